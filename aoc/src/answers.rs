@@ -0,0 +1,270 @@
+// The registry of known-good answers, one `days/NN/answers.toml` per day:
+//
+//     part1 = "490"
+//     part2 = "70357"
+//
+// Once a day's answers are accepted on the site, they're recorded here so
+// that `aoc verify` can catch a refactor that quietly breaks a solution.
+// `aoc submit` records them itself, along with the answers the site turned
+// down, so that those are never sent again:
+//
+//     [[wrong]]
+//     part = 2
+//     answer = "70400"
+//     hint = "too high"
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use aoc_common::normalize;
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    #[serde(default)]
+    pub wrong: Vec<Wrong>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Wrong {
+    pub part: u8,
+    pub answer: String,
+    pub hint: Option<Hint>,
+}
+
+// What the site said about a wrong answer, if anything.
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq)]
+pub enum Hint {
+    #[serde(rename = "too high")]
+    TooHigh,
+    #[serde(rename = "too low")]
+    TooLow,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::TooHigh => write!(f, "too high"),
+            Hint::TooLow => write!(f, "too low"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn path(day: u8) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "..", "days", &format!("{:02}", day), "answers.toml"]
+            .iter().collect()
+    }
+
+    // Read a day's answers. A day without an answers file just hasn't had
+    // any recorded yet.
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e)),
+        };
+        toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+
+    // Whether `answer` is worth sending to the site: not for a part that's
+    // already solved, and not if it's been turned down before - or would be,
+    // given what the site said about the answers it turned down.
+    pub fn vet(&self, part: u8, answer: &str) -> Result<(), String> {
+        match self.part(part) {
+            Some(known) if known == answer => return Err(format!("part {} is already solved: {}", part, known)),
+            Some(known) => return Err(format!("part {} was already solved with {}, not {}", part, known, answer)),
+            None => {},
+        }
+
+        let number = answer.parse::<i64>().ok();
+        for wrong in self.wrong.iter().filter(|w| w.part == part) {
+            let bound = wrong.answer.parse::<i64>().ok();
+            let hint = wrong.hint.map_or(String::new(), |hint| format!(" ({})", hint));
+            if wrong.answer == answer {
+                return Err(format!("{} was already turned down{}", answer, hint));
+            }
+            match (number, wrong.hint, bound) {
+                (Some(n), Some(Hint::TooHigh), Some(bound)) if n > bound =>
+                    return Err(format!("{} is too high: {} already was", answer, bound)),
+                (Some(n), Some(Hint::TooLow), Some(bound)) if n < bound =>
+                    return Err(format!("{} is too low: {} already was", answer, bound)),
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+}
+
+fn quote(answer: &str) -> String {
+    toml::Value::String(answer.to_string()).to_string()
+}
+
+fn update(path: &Path, edit: impl FnOnce(String) -> String) -> Result<(), String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e)),
+    };
+    fs::write(path, edit(text)).map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+}
+
+// Record an accepted answer. The file is edited rather than rewritten, to
+// keep any comments in it, and the answer goes before any `[[wrong]]` tables
+// (where it would otherwise end up inside the last of them).
+pub fn record(path: &Path, part: u8, answer: &str) -> Result<(), String> {
+    update(path, |text| {
+        let table = if text.starts_with('[') { Some(0) } else { text.find("\n[").map(|idx| idx + 1) };
+        let (keys, tables) = text.split_at(table.unwrap_or(text.len()));
+        let mut out = keys.trim_end().to_string();
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out += &format!("part{} = {}\n", part, quote(answer));
+        if !tables.is_empty() {
+            out = out + "\n" + tables;
+        }
+        out
+    })
+}
+
+// Record an answer the site turned down.
+pub fn record_wrong(path: &Path, part: u8, answer: &str, hint: Option<Hint>) -> Result<(), String> {
+    update(path, |text| {
+        let mut table = format!("[[wrong]]\npart = {}\nanswer = {}\n", part, quote(answer));
+        if let Some(hint) = hint {
+            table += &format!("hint = {}\n", quote(&hint.to_string()));
+        }
+        match text.as_str() {
+            "" => table,
+            text => format!("{}\n\n{}", text.trim_end(), table),
+        }
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Missing,
+}
+
+pub fn check(expected: Option<&str>, actual: &str) -> Verdict {
+    match expected {
+        None => Verdict::Missing,
+        Some(expected) if normalize(expected) == normalize(actual) => Verdict::Pass,
+        Some(_) => Verdict::Fail,
+    }
+}
+
+// Show how an answer differs from the recorded one: side by side for short
+// answers, or line by line (`-` expected, `+` actual) for longer ones.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let (expected, actual) = (normalize(expected), normalize(actual));
+    if expected.len() <= 1 && actual.len() <= 1 {
+        return format!("expected: {}\n     got: {}\n",
+                       expected.first().unwrap_or(&""), actual.first().unwrap_or(&""));
+    }
+
+    let mut out = String::new();
+    for idx in 0..expected.len().max(actual.len()) {
+        match (expected.get(idx), actual.get(idx)) {
+            (Some(e), Some(a)) if e == a => out += &format!("  {}\n", e),
+            (e, a) => {
+                if let Some(e) = e { out += &format!("- {}\n", e) }
+                if let Some(a) = a { out += &format!("+ {}\n", a) }
+            },
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_file() {
+        let answers: Answers = toml::from_str("part1 = \"490\"\npart2 = '''\n#  #\n####\n'''").unwrap();
+        assert_eq!(answers.part(1), Some("490"));
+        assert_eq!(answers.part(2), Some("#  #\n####\n"));
+        assert_eq!(toml::from_str::<Answers>("part2 = \"x\"").unwrap().part(1), None);
+
+        let missing = Answers::load(Path::new("no/such/answers.toml")).unwrap();
+        assert_eq!(missing, Answers::default());
+        assert!(Answers::load(&Answers::path(1)).unwrap().part1.is_some());
+    }
+
+    #[test]
+    fn vetting() {
+        let answers: Answers = toml::from_str("part1 = \"490\"\n\n\
+            [[wrong]]\npart = 2\nanswer = \"700\"\nhint = \"too high\"\n\n\
+            [[wrong]]\npart = 2\nanswer = \"300\"\nhint = \"too low\"\n\n\
+            [[wrong]]\npart = 2\nanswer = \"abc\"\n").unwrap();
+        assert_eq!(answers.vet(1, "490"), Err(String::from("part 1 is already solved: 490")));
+        assert!(answers.vet(1, "491").is_err());
+        assert_eq!(answers.vet(2, "700"), Err(String::from("700 was already turned down (too high)")));
+        assert_eq!(answers.vet(2, "701"), Err(String::from("701 is too high: 700 already was")));
+        assert_eq!(answers.vet(2, "-5"), Err(String::from("-5 is too low: 300 already was")));
+        assert!(answers.vet(2, "abc").is_err());
+        assert_eq!(answers.vet(2, "500"), Ok(()));
+        assert_eq!(answers.vet(2, "xyz"), Ok(()));
+    }
+
+    #[test]
+    fn recording() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+        record_wrong(&path, 1, "12", Some(Hint::TooLow)).unwrap();
+        record(&path, 1, "490").unwrap();
+        record_wrong(&path, 2, "a\"b", None).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\
+part1 = \"490\"
+
+[[wrong]]
+part = 1
+answer = \"12\"
+hint = \"too low\"
+
+[[wrong]]
+part = 2
+answer = \"a\\\"b\"
+");
+        let answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.part(1), Some("490"));
+        assert_eq!(answers.wrong[1], Wrong { part: 2, answer: String::from("a\"b"), hint: None });
+
+        // Comments are left where they are.
+        fs::write(&path, "# Found by hand.\npart1 = \"490\"").unwrap();
+        record(&path, 2, "70357").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Found by hand.\npart1 = \"490\"\npart2 = \"70357\"\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(check(Some("490"), "490"), Verdict::Pass);
+        assert_eq!(check(Some("490"), "491"), Verdict::Fail);
+        assert_eq!(check(None, "490"), Verdict::Missing);
+        assert_eq!(check(Some("#  #\n####\n"), "#  #  \n####"), Verdict::Pass);
+    }
+
+    #[test]
+    fn diffs() {
+        assert_eq!(diff("490", "491"), "expected: 490\n     got: 491\n");
+        assert_eq!(diff("#  #\n####\n#  #", "#  #\n## #\n#  #"), "  #  #\n- ####\n+ ## #\n  #  #\n");
+        assert_eq!(diff("a\nb", "a"), "  a\n- b\n");
+    }
+}
@@ -0,0 +1,209 @@
+// A small statistical timer for the puzzles. Each stage of a day (parsing,
+// then each part) is run over and over until it's been sampled enough, and
+// the median time is compared against the last run's, kept on disk.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+// The stages of a day that get timed, in the order they run.
+pub const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+// How long to spend sampling each stage.
+pub struct Sampler {
+    pub budget: Duration,
+    pub min_samples: usize,
+    pub max_samples: usize,
+}
+
+impl Default for Sampler {
+    fn default() -> Sampler {
+        Sampler{ budget: Duration::from_secs(1), min_samples: 5, max_samples: 100 }
+    }
+}
+
+impl Sampler {
+    // Time `f` at least `min_samples` times, and then keep going until either
+    // the time budget runs out or there are plenty of samples.
+    pub fn sample<T>(&self, mut f: impl FnMut() -> T) -> Stats {
+        let start = Instant::now();
+        let mut samples = Vec::new();
+        while samples.len() < self.min_samples
+            || (samples.len() < self.max_samples && start.elapsed() < self.budget)
+        {
+            let now = Instant::now();
+            std::hint::black_box(f());
+            samples.push(now.elapsed());
+        }
+        Stats::new(samples)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub samples: usize,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Stats {
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n % 2 == 1 { samples[n / 2] } else { (samples[n / 2 - 1] + samples[n / 2]) / 2 };
+        Stats{ median, min: samples[0], max: samples[n - 1], samples: n }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:>10.1?}  ({:.1?} .. {:.1?}, {} runs)", self.median, self.min, self.max, self.samples)
+    }
+}
+
+// How a stage's time compares to the baseline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Change {
+    New,
+    Unchanged(f64),
+    Improved(f64),
+    Regressed(f64),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::New => write!(f, "new"),
+            Change::Unchanged(pct) => write!(f, "{:+.1}%", pct),
+            Change::Improved(pct) => write!(f, "{:+.1}% improved", pct),
+            Change::Regressed(pct) => write!(f, "{:+.1}% REGRESSED", pct),
+        }
+    }
+}
+
+// The median time of every stage from the last run, keyed by (day, stage).
+// On disk it's one tab separated line per stage: `day stage nanoseconds`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    times: BTreeMap<(u8, String), Duration>,
+}
+
+impl Baseline {
+    // Where the baseline lives unless told otherwise. Timings only make sense
+    // on the machine they were taken on, so it's kept out of the repo.
+    pub fn default_path() -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "..", "target", "aoc-bench", "baseline.tsv"].iter().collect()
+    }
+
+    // Read the baseline, which is empty when there hasn't been a run yet.
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        match fs::read_to_string(path) {
+            Ok(text) => Baseline::parse(&text)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData,
+                                            format!("{}: {}", path.display(), e))),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    fn parse(text: &str) -> Result<Baseline, String> {
+        let mut times = BTreeMap::new();
+        for (idx, line) in text.lines().enumerate().filter(|(_, line)| !line.is_empty()) {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let (day, stage, nanos) = match fields.as_slice() {
+                [day, stage, nanos] => (day.parse::<u8>(), stage, nanos.parse::<u64>()),
+                _ => return Err(format!("line {}: expected `day<TAB>stage<TAB>nanoseconds`", idx + 1)),
+            };
+            match (day, nanos) {
+                (Ok(day), Ok(nanos)) => times.insert((day, stage.to_string()), Duration::from_nanos(nanos)),
+                _ => return Err(format!("line {}: expected numbers for the day and time", idx + 1)),
+            };
+        }
+        Ok(Baseline{ times })
+    }
+
+    pub fn record(&mut self, day: u8, stage: &str, stats: &Stats) {
+        self.times.insert((day, stage.to_string()), stats.median);
+    }
+
+    // Compare a new timing against the baseline. Timings within `threshold`
+    // percent either way are just noise.
+    pub fn compare(&self, day: u8, stage: &str, stats: &Stats, threshold: f64) -> Change {
+        let old = match self.times.get(&(day, stage.to_string())) {
+            Some(old) if !old.is_zero() => old.as_secs_f64(),
+            _ => return Change::New,
+        };
+        let pct = (stats.median.as_secs_f64() - old) / old * 100.0;
+        if pct > threshold {
+            Change::Regressed(pct)
+        } else if pct < -threshold {
+            Change::Improved(pct)
+        } else {
+            Change::Unchanged(pct)
+        }
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ((day, stage), time) in &self.times {
+            writeln!(f, "{}\t{}\t{}", day, stage, time.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(millis: &[u64]) -> Stats {
+        Stats::new(millis.iter().map(|&ms| Duration::from_millis(ms)).collect())
+    }
+
+    #[test]
+    fn statistics() {
+        let odd = stats(&[9, 1, 5]);
+        assert_eq!((odd.median, odd.min, odd.max, odd.samples),
+                   (Duration::from_millis(5), Duration::from_millis(1), Duration::from_millis(9), 3));
+        assert_eq!(stats(&[4, 1, 2, 9]).median, Duration::from_millis(3));
+
+        let sampler = Sampler{ budget: Duration::from_secs(0), min_samples: 3, max_samples: 10 };
+        assert_eq!(sampler.sample(|| 1 + 1).samples, 3);
+    }
+
+    #[test]
+    fn baseline() {
+        let mut baseline = Baseline::default();
+        baseline.record(7, "part1", &stats(&[100]));
+        baseline.record(14, "parse", &stats(&[2]));
+        let text = baseline.to_string();
+        assert_eq!(text, "7\tpart1\t100000000\n14\tparse\t2000000\n");
+        assert_eq!(Baseline::parse(&text), Ok(baseline));
+        assert!(Baseline::parse("7 part1 100").is_err());
+        assert!(Baseline::parse("seven\tpart1\t100").is_err());
+    }
+
+    #[test]
+    fn regressions() {
+        let mut baseline = Baseline::default();
+        baseline.record(7, "part1", &stats(&[100]));
+
+        let change = |day, stage, ms| baseline.compare(day, stage, &stats(&[ms]), 10.0);
+        assert!(matches!(change(7, "part1", 105), Change::Unchanged(pct) if (pct - 5.0).abs() < 1e-9));
+        assert!(matches!(change(7, "part1", 150), Change::Regressed(pct) if (pct - 50.0).abs() < 1e-9));
+        assert!(matches!(change(7, "part1", 50), Change::Improved(pct) if (pct + 50.0).abs() < 1e-9));
+        assert_eq!(change(7, "part2", 50), Change::New);
+    }
+}
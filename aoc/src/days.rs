@@ -0,0 +1,100 @@
+// The registry of every day's solutions. Each day implements `Solution` with
+// its own input and answer types, so they're wrapped up behind `Puzzle` to
+// let the runner keep them all in one list.
+
+use std::any::Any;
+use std::marker::PhantomData;
+
+use std::hint::black_box;
+
+use aoc_common::Answer;
+use aoc_common::diagnostic::Diagnostic;
+use aoc_common::generate::{self, Generate};
+
+use crate::bench::{Sampler, Stats};
+
+// A type-erased `Solution`, using the puzzle's default config.
+pub trait Puzzle {
+    fn day(&self) -> u8;
+    fn parse(&self, puzzle: &str) -> Result<Box<dyn Any>, Box<dyn Diagnostic>>;
+    fn part1(&self, input: &dyn Any) -> Answer<String>;
+    fn part2(&self, input: &dyn Any) -> Answer<String>;
+
+    fn part(&self, part: u8, input: &dyn Any) -> Answer<String> {
+        match part {
+            1 => self.part1(input),
+            _ => self.part2(input),
+        }
+    }
+
+    // A made-up input, of the day's usual size unless given one.
+    fn generate(&self, seed: u64, size: Option<usize>) -> String;
+
+    // Time parsing the puzzle, and then each part, with the answers unerased
+    // so that turning them into strings isn't part of the measurement.
+    fn bench(&self, puzzle: &str, sampler: &Sampler) -> Result<[Stats; 3], Box<dyn Diagnostic>>;
+}
+
+struct Erased<S>(PhantomData<S>);
+
+impl<S: Generate> Erased<S> {
+    fn input<'a>(&self, input: &'a dyn Any) -> &'a S::Input {
+        input.downcast_ref().expect("input parsed by a different day")
+    }
+}
+
+impl<S: Generate> Puzzle for Erased<S> {
+    fn day(&self) -> u8 { S::DAY }
+
+    fn parse(&self, puzzle: &str) -> Result<Box<dyn Any>, Box<dyn Diagnostic>> {
+        match S::parse(puzzle) {
+            Ok(input) => Ok(Box::new(input)),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
+    fn part1(&self, input: &dyn Any) -> Answer<String> {
+        S::part1(self.input(input), &S::Config::default()).map(|answer| answer.to_string())
+    }
+
+    fn part2(&self, input: &dyn Any) -> Answer<String> {
+        S::part2(self.input(input), &S::Config::default()).map(|answer| answer.to_string())
+    }
+
+    fn generate(&self, seed: u64, size: Option<usize>) -> String {
+        generate::generate::<S>(seed, size.unwrap_or(S::SIZE))
+    }
+
+    fn bench(&self, puzzle: &str, sampler: &Sampler) -> Result<[Stats; 3], Box<dyn Diagnostic>> {
+        let input = S::parse(puzzle).map_err(|e| Box::new(e) as Box<dyn Diagnostic>)?;
+        let config = S::Config::default();
+        Ok([
+            sampler.sample(|| S::parse(black_box(puzzle))),
+            sampler.sample(|| S::part1(black_box(&input), &config)),
+            sampler.sample(|| S::part2(black_box(&input), &config)),
+        ])
+    }
+}
+
+fn puzzle<S: Generate + 'static>() -> Box<dyn Puzzle> {
+    Box::new(Erased::<S>(PhantomData))
+}
+
+pub fn all() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        puzzle::<aoc_2018_01::Day01>(),
+        puzzle::<aoc_2018_02::Day02>(),
+        puzzle::<aoc_2018_03::Day03>(),
+        puzzle::<aoc_2018_04::Day04>(),
+        puzzle::<aoc_2018_05::Day05>(),
+        puzzle::<aoc_2018_06::Day06>(),
+        puzzle::<aoc_2018_07::Day07>(),
+        puzzle::<aoc_2018_08::Day08>(),
+        puzzle::<aoc_2018_09::Day09>(),
+        puzzle::<aoc_2018_10::Day10>(),
+        puzzle::<aoc_2018_11::Day11>(),
+        puzzle::<aoc_2018_12::Day12>(),
+        puzzle::<aoc_2018_13::Day13>(),
+        puzzle::<aoc_2018_14::Day14>(),
+    ]
+}
@@ -0,0 +1,400 @@
+// Pulling the worked examples out of a day's README, to keep as fixtures
+// (see `aoc_common::examples`). The README's code blocks hold the example
+// inputs - along with diagrams, and other things that aren't inputs at all,
+// so only blocks the day's parser accepts are kept. The answers are stated
+// in the prose that follows, usually emphasized: "the largest area is *17*".
+//
+// READMEs come in two styles: fenced blocks and `*emphasis*` from `aoc
+// fetch`, or the indented blocks and `_emphasis_` of older html2text ones.
+
+use std::fs;
+use std::path::Path;
+
+use aoc_common::examples::{Example, Examples};
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Code(String),
+    Prose(String),
+}
+
+// Split a README into code and prose, noting which part of the puzzle each
+// belongs to.
+fn segments(readme: &str) -> Vec<(u8, Segment)> {
+    let lines = readme.lines().collect::<Vec<_>>();
+    let blank = |line: &str| line.trim().is_empty();
+    let indented = |line: &str| line.starts_with("    ") && !blank(line);
+
+    let mut segments = Vec::new();
+    let mut prose = String::new();
+    let mut part = 1;
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx];
+        let fence = line.trim_start().chars().take_while(|&c| c == '`').count();
+        let code = if fence >= 3 {
+            let end = lines[idx + 1..].iter()
+                .position(|l| l.trim().len() >= fence && l.trim().chars().all(|c| c == '`'))
+                .map_or(lines.len(), |end| idx + 1 + end);
+            let code = lines[idx + 1..end].join("\n");
+            idx = end + 1;
+            Some(code)
+        } else if indented(line) && (idx == 0 || blank(lines[idx - 1])) {
+            let end = lines[idx..].iter()
+                .position(|l| !blank(l) && !l.starts_with("    "))
+                .map_or(lines.len(), |end| idx + end);
+            let mut code = lines[idx..end].iter()
+                .map(|l| l.get(4..).unwrap_or(""))
+                .collect::<Vec<_>>();
+            while code.last().is_some_and(|l| blank(l)) {
+                code.pop();
+            }
+            // html2text starts every block with a few stray blank lines, and
+            // indents the first real one by three more spaces than the rest.
+            let start = code.iter().position(|l| !blank(l)).unwrap_or(code.len());
+            let mut code = code[start..].iter().map(|l| l.to_string()).collect::<Vec<_>>();
+            if let Some(first) = code.first_mut() {
+                let stray = first.len() - first.trim_start().len();
+                first.replace_range(..stray.min(3), "");
+            }
+            idx = end;
+            Some(code.join("\n"))
+        } else {
+            None
+        };
+
+        match code {
+            Some(code) => {
+                segments.push((part, Segment::Prose(std::mem::take(&mut prose))));
+                segments.push((part, Segment::Code(code)));
+            },
+            None => {
+                if line.starts_with("##") && line.contains("Part Two") {
+                    segments.push((part, Segment::Prose(std::mem::take(&mut prose))));
+                    part = 2;
+                }
+                prose += line;
+                prose.push('\n');
+                idx += 1;
+            },
+        }
+    }
+    segments.push((part, Segment::Prose(prose)));
+    segments.retain(|(_, s)| !matches!(s, Segment::Prose(p) if p.trim().is_empty()));
+    segments
+}
+
+// The text of some markup, without the markup: "` 1+1= _2_ `" is "1+1= 2".
+fn plain(text: &str) -> String {
+    text.replace(['`', '_', '*'], "").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Whether some (plain) text looks like it could be a puzzle's answer: a
+// number, coordinates, or a code like `CABDFE`, rather than a word.
+fn answer(text: &str) -> Option<String> {
+    let text = text.rsplit('=').next().unwrap_or("").trim();
+    let plausible = !text.is_empty()
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == ',' || c == '-')
+        && (text.chars().any(|c| c.is_ascii_digit()) || text.chars().all(|c| c.is_ascii_uppercase()));
+    if plausible { Some(text.to_string()) } else { None }
+}
+
+// The emphasized spans of some prose, and whether each was also code - which
+// the site uses to highlight an example's answer.
+fn emphasized(prose: &str) -> Vec<(String, bool)> {
+    // Links are noise here, and URLs are full of underscores.
+    let mut text = String::new();
+    let mut rest = prose;
+    while let Some(start) = rest.find("](") {
+        text += &rest[..start];
+        let mut escaped = false;
+        let end = rest[start..].char_indices().find(|&(_, c)| {
+            let close = c == ')' && !escaped;
+            escaped = c == '\\' && !escaped;
+            close
+        });
+        rest = end.map_or("", |(end, _)| &rest[start + end + 1..]);
+    }
+    text += rest;
+
+    let mut spans = Vec::new();
+    let mut in_code = false;
+    let mut open: Option<(char, usize, bool)> = None;
+    let mut escaped = false;
+    for (idx, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '`' => in_code = !in_code,
+            '_' | '*' => match open {
+                Some((delim, start, code)) if delim == c => {
+                    let inner = &text[start + 1..idx];
+                    spans.push((plain(inner), code || in_code || inner.contains('`')));
+                    open = None;
+                },
+                Some(_) => {},
+                None => open = Some((c, idx, in_code)),
+            },
+            _ => {},
+        }
+    }
+    spans
+}
+
+// The answer some prose gives for an example. The site's phrasing varies, so
+// in order of preference: "the answer would be `10 * 24 = 240`", the last
+// emphasized code ("the correct order is *`CABDFE`*"), or the first
+// emphasized number ("has a total size of *16*").
+fn stated_answer(prose: &str) -> Option<String> {
+    // List bullets aren't emphasis, though they look a lot like it.
+    let flat = prose.lines()
+        .map(|line| line.trim_start())
+        .map(|line| line.strip_prefix("* ").or_else(|| line.strip_prefix("- ")).unwrap_or(line))
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Some(idx) = flat.find("answer would be") {
+        let after = &flat[idx + "answer would be".len()..];
+        let code = after.trim_start().strip_prefix('`')
+            .and_then(|code| code.find('`').map(|end| &code[..end]));
+        if let Some(found) = code.and_then(|code| answer(&plain(code))) {
+            return Some(found);
+        }
+    }
+    let spans = emphasized(&flat);
+    spans.iter().rev().filter(|(_, code)| *code).find_map(|(text, _)| answer(text))
+        .or_else(|| spans.iter().find_map(|(text, _)| answer(text)))
+}
+
+// The puzzle input in a code block, if there is one. Examples that play out
+// over several steps often show them all in one block, one after the other,
+// in which case the first step is the input.
+fn input<'a>(code: &'a str, accepts: &dyn Fn(&str) -> bool) -> Option<&'a str> {
+    let first = code.split("\n\n").next().unwrap_or(code);
+    if accepts(code) {
+        Some(code)
+    } else if first.len() < code.len() && accepts(first) {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+// The examples in a README: each code block that `accepts` takes as puzzle
+// input, with the answers stated for it. A second part without any examples
+// of its own uses the last one from the first part.
+pub fn extract(readme: &str, accepts: &dyn Fn(&str) -> bool) -> Vec<(String, [Option<String>; 2])> {
+    let segments = segments(readme);
+    let mut examples: Vec<(String, [Option<String>; 2])> = Vec::new();
+
+    for part in 1..=2 {
+        let section = segments.iter().filter(|(p, _)| *p == part).map(|(_, s)| s).collect::<Vec<_>>();
+        let inputs = section.iter().enumerate()
+            .filter_map(|(idx, s)| match s {
+                Segment::Code(code) => input(code, accepts).map(|input| (idx, input)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let prose = |from: usize, to: usize| section[from..to].iter()
+            .filter_map(|s| match s { Segment::Prose(p) => Some(p.as_str()), _ => None })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if inputs.is_empty() && part == 2 {
+            if let Some(last) = examples.last_mut() {
+                last.1[1] = stated_answer(&prose(0, section.len()));
+            }
+            continue;
+        }
+        for (n, &(idx, input)) in inputs.iter().enumerate() {
+            let stated = stated_answer(&prose(idx + 1, inputs.get(n + 1).map_or(section.len(), |&(next, _)| next)));
+            match examples.iter_mut().find(|(existing, _)| existing == input) {
+                Some(existing) => existing.1[part as usize - 1] = stated,
+                None => {
+                    let mut answers = [None, None];
+                    answers[part as usize - 1] = stated;
+                    examples.push((input.to_string(), answers));
+                },
+            }
+        }
+    }
+    examples
+}
+
+// Bring a day's fixtures in `dir` up to date with its README. Answers that
+// are already recorded are kept, since they may have been fixed up by hand.
+pub fn update(dir: &Path, accepts: &dyn Fn(&str) -> bool) -> Result<Examples, String> {
+    let path = dir.join("README.md");
+    let readme = fs::read_to_string(&path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+    let mut fixtures = Examples::load(dir)?;
+
+    for (input, [part1, part2]) in extract(&readme, accepts) {
+        let existing = fixtures.examples.iter_mut().find(|example| {
+            fs::read_to_string(dir.join(&example.input)).is_ok_and(|text| text.trim_end() == input.trim_end())
+        });
+        let example = match existing {
+            Some(example) => example,
+            None => {
+                let name = format!("examples/{}.txt", fixtures.examples.len() + 1);
+                let path = dir.join(&name);
+                fs::create_dir_all(dir.join("examples"))
+                    .and_then(|_| fs::write(&path, input + "\n"))
+                    .map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+                fixtures.examples.push(Example{ input: name, ..Example::default() });
+                fixtures.examples.last_mut().unwrap()
+            },
+        };
+        example.part1 = example.part1.take().or(part1);
+        example.part2 = example.part2.take().or(part2);
+    }
+
+    if !fixtures.examples.is_empty() {
+        let path = dir.join("examples.toml");
+        fs::write(&path, fixtures.to_toml()).map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+    }
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An html2text README, as the early days have.
+    static OLD: &str = "\
+##  \\--- Day 6: Chronal Coordinates ---
+
+Using only the [ Manhattan distance
+](https://en.wikipedia.org/wiki/Taxicab_geometry) , determine the _area_
+around each coordinate. For example, given the following list of coordinates:
+
+
+
+
+       1, 1
+    1, 6
+    8, 3
+
+
+
+Visualizing these coordinates on a grid:
+
+
+
+
+       ..........
+    .A........
+
+
+
+Therefore, in this example, the size of the largest area is _17_ .
+
+_What is the size of the largest area_ that isn 't infinite?
+
+##  \\--- Part Two ---
+
+Every location is _within_ the region, which has a total size of _16_
+.
+
+Your actual region will need to be much larger: less than _10000_ .
+";
+
+    // One written by `aoc fetch`.
+    static NEW: &str = "\
+## --- Day 7: The Sum of Its Parts ---
+
+For example:
+
+```
+Step C must be finished before step A can begin.
+Step A must be finished before step B can begin.
+```
+
+- Only *`C`* is available, and so it is done first.
+- *`A`* is first alphabetically.
+
+So, in this example, the correct order is *`CAB`*.
+
+*In what order should the steps be completed?*
+
+## --- Part Two ---
+
+Here is another example:
+
+```
+Step X must be finished before step Y can begin.
+```
+
+In this example, it would take *15* seconds for two workers to complete these steps.
+";
+
+    #[test]
+    fn code_blocks() {
+        let segments = segments(OLD);
+        let code = segments.iter().filter_map(|(part, s)| match s {
+            Segment::Code(code) => Some((*part, code.as_str())),
+            _ => None,
+        }).collect::<Vec<_>>();
+        assert_eq!(code, [(1, "1, 1\n1, 6\n8, 3"), (1, "..........\n.A........")]);
+
+        assert_eq!(extract(NEW, &|code| code.starts_with("Step")), [
+            (String::from("Step C must be finished before step A can begin.\n\
+                           Step A must be finished before step B can begin."), [Some(String::from("CAB")), None]),
+            (String::from("Step X must be finished before step Y can begin."), [None, Some(String::from("15"))]),
+        ]);
+
+        // A block of several steps starts with the input.
+        let one_step = |code: &str| !code.contains("\n\n");
+        assert_eq!(input("/->-\\\n\\---/\n\n/-->\\\n\\---/", &one_step), Some("/->-\\\n\\---/"));
+        assert_eq!(input("..#\n\n.#.", &|code| code.starts_with('#')), None);
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(extract(OLD, &|code| code.contains(", ")),
+                   [(String::from("1, 1\n1, 6\n8, 3"), [Some(String::from("17")), Some(String::from("16"))])]);
+        assert_eq!(stated_answer("the answer would be ` 10 * 24 = 240 ` ."), Some(String::from("240")));
+        assert_eq!(stated_answer("that sum is ` 1+1+2+10+11+12+2+99= _138_ `"), Some(String::from("138")));
+        assert_eq!(stated_answer("after _` 20 ` generations _ it's ` _325_ `"), Some(String::from("325")));
+        assert_eq!(stated_answer("_What is the size of the largest area?_"), None);
+    }
+
+    #[test]
+    fn fixtures() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("README.md"), OLD).unwrap();
+        let accepts = |code: &str| code.contains(", ");
+
+        let fixtures = update(&dir, &accepts).unwrap();
+        assert_eq!(fixtures.examples.len(), 1);
+        assert_eq!(fs::read_to_string(dir.join("examples/1.txt")).unwrap(), "1, 1\n1, 6\n8, 3\n");
+
+        // Hand-made corrections survive another update.
+        fs::write(dir.join("examples.toml"), "[[example]]\ninput = \"examples/1.txt\"\npart1 = \"18\"\n").unwrap();
+        let fixtures = update(&dir, &accepts).unwrap();
+        assert_eq!(fixtures.examples[0].part1.as_deref(), Some("18"));
+        assert_eq!(fixtures.examples[0].part2.as_deref(), Some("16"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_fixtures() {
+        use std::panic::catch_unwind;
+
+        use aoc_2018_02::Day02;
+        use aoc_2018_02::matching::Distance;
+        use aoc_common::examples::{none_yet, test};
+
+        // A day's examples can't go missing without its tests noticing...
+        let missing = Path::new("no/such/day");
+        assert!(catch_unwind(|| test::<Day02>(missing, &Distance::default())).is_err());
+        none_yet::<Day02>(missing);
+        // ...nor can a new day's turn up without being tested.
+        let day2 = aoc_common::input::default_path(2).with_file_name("");
+        test::<Day02>(&day2, &Distance::default());
+        assert!(catch_unwind(|| none_yet::<Day02>(&day2)).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+// Fetching puzzle descriptions and inputs from the Advent of Code site, and
+// sending answers back to it.
+//
+// Inputs are different for everyone, so fetching needs the `session` cookie
+// from a logged in browser. It's read from the `AOC_SESSION` environment
+// variable, or from the config file (`~/.config/aoc-2018/config.toml`, or
+// wherever `AOC_CONFIG` points):
+//
+//     session = "53616c7465645f5f..."
+//     # All optional:
+//     base_url = "https://adventofcode.com"
+//     interval = 3.0   # seconds to wait between requests
+//     cache = "/home/me/.cache/aoc-2018"
+//
+// Everything fetched is cached, so the site only ever sees a request once,
+// and never more often than every `interval` seconds (answers included).
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+
+const YEAR: u16 = 2018;
+const USER_AGENT: &str = "github.com/tbutts/aoc-2018-rust by dapegral@gmail.com";
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession { config: Option<PathBuf> },
+    Config { path: PathBuf, err: String },
+    Status { url: String, code: u16 },
+    // ureq's errors already name the url.
+    Transport(String),
+    Io { path: PathBuf, err: io::Error },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::NoSession { config } => {
+                write!(f, "no session cookie to fetch with: set AOC_SESSION")?;
+                match config {
+                    Some(path) => write!(f, ", or `session = \"...\"` in {}", path.display()),
+                    None => Ok(()),
+                }
+            },
+            FetchError::Config { path, err } => write!(f, "bad config in {}: {}", path.display(), err),
+            FetchError::Status { url, code } => {
+                write!(f, "couldn't fetch {}: HTTP {}", url, code)?;
+                match code {
+                    400 | 401 | 403 => write!(f, " (has the session cookie expired?)"),
+                    404 => write!(f, " (is the puzzle unlocked yet?)"),
+                    _ => Ok(()),
+                }
+            },
+            FetchError::Transport(err) => write!(f, "couldn't fetch {}", err),
+            FetchError::Io { path, err } => write!(f, "couldn't write {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub interval: Duration,
+    pub cache: PathBuf,
+    // Where the config came from, to point at when the session is missing.
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    session: Option<String>,
+    base_url: Option<String>,
+    interval: Option<f64>,
+    cache: Option<PathBuf>,
+}
+
+fn home_dir(xdg: &str, fallback: &str) -> Option<PathBuf> {
+    env::var_os(xdg).map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(fallback)))
+}
+
+impl Config {
+    // Read the config file (if there is one), with the environment's session
+    // taking priority over the file's.
+    pub fn load() -> Result<Config, FetchError> {
+        let path = env::var_os("AOC_CONFIG").map(PathBuf::from)
+            .or_else(|| home_dir("XDG_CONFIG_HOME", ".config").map(|dir| dir.join("aoc-2018/config.toml")));
+        let text = match &path {
+            Some(path) => match fs::read_to_string(path) {
+                Ok(text) => Some(text),
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => None,
+                Err(e) => return Err(FetchError::Config { path: path.clone(), err: e.to_string() }),
+            },
+            None => None,
+        };
+        Config::parse(path, text.as_deref(), env::var("AOC_SESSION").ok())
+    }
+
+    fn parse(path: Option<PathBuf>, text: Option<&str>, session: Option<String>) -> Result<Config, FetchError> {
+        let file: ConfigFile = match (text, &path) {
+            (Some(text), Some(p)) => toml::from_str(text)
+                .map_err(|e| FetchError::Config { path: p.clone(), err: e.to_string() })?,
+            _ => ConfigFile::default(),
+        };
+        let cache = file.cache
+            .or_else(|| home_dir("XDG_CACHE_HOME", ".cache").map(|dir| dir.join("aoc-2018")))
+            .unwrap_or_else(|| [env!("CARGO_MANIFEST_DIR"), "..", "target", "aoc-cache"].iter().collect());
+        Ok(Config {
+            session: session.or(file.session).map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+            base_url: file.base_url.unwrap_or_else(|| String::from("https://adventofcode.com")),
+            interval: file.interval.and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .unwrap_or(Duration::from_secs(3)),
+            cache,
+            path,
+        })
+    }
+}
+
+pub struct Fetcher {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl Fetcher {
+    pub fn new(config: Config) -> Fetcher {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Fetcher{ config, agent }
+    }
+
+    // The site being fetched from, which the puzzle's links are relative to.
+    pub fn base_url(&self) -> &str {
+        &self.config.base_url
+    }
+
+    // Where a fetched page ends up in the cache.
+    pub fn cached(&self, name: &str) -> PathBuf {
+        self.config.cache.join(YEAR.to_string()).join(name)
+    }
+
+    // The puzzle's description page. It grows a second part once the first
+    // is solved, so `refresh` skips the cache to pick that up.
+    pub fn puzzle(&self, day: u8, refresh: bool) -> Result<String, FetchError> {
+        self.get(&format!("day/{}", day), &format!("day{:02}.html", day), refresh, false)
+    }
+
+    // The day's puzzle input, which never changes once it's been given out.
+    pub fn input(&self, day: u8) -> Result<String, FetchError> {
+        self.get(&format!("day/{}/input", day), &format!("day{:02}.input", day), false, true)
+    }
+
+    fn get(&self, page: &str, name: &str, refresh: bool, needs_session: bool) -> Result<String, FetchError> {
+        let cached = self.cached(name);
+        if !refresh {
+            if let Ok(text) = fs::read_to_string(&cached) {
+                return Ok(text);
+            }
+        }
+        let session = self.config.session.as_ref();
+        if needs_session && session.is_none() {
+            return Err(FetchError::NoSession { config: self.config.path.clone() });
+        }
+
+        let url = self.url(page);
+        self.wait_politely();
+        let mut request = self.agent.get(&url);
+        if let Some(session) = session {
+            request = request.set("Cookie", &format!("session={}", session));
+        }
+        let response = request.call();
+        self.note_request();
+
+        let text = read(url, response)?;
+        write(&cached, &text)?;
+        Ok(text)
+    }
+
+    // Post an answer to one part of a day's puzzle, returning the site's
+    // reply. Every answer counts, so this is never cached or retried.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<String, FetchError> {
+        let session = self.config.session.as_ref()
+            .ok_or_else(|| FetchError::NoSession { config: self.config.path.clone() })?;
+
+        let url = self.url(&format!("day/{}/answer", day));
+        self.wait_politely();
+        let response = self.agent.post(&url)
+            .set("Cookie", &format!("session={}", session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        self.note_request();
+        read(url, response)
+    }
+
+    fn url(&self, page: &str) -> String {
+        format!("{}/{}/{}", self.config.base_url.trim_end_matches('/'), YEAR, page)
+    }
+
+    // The time of the last request is kept in the cache, so that separate
+    // runs of `aoc` are just as polite as one long one.
+    fn last_request_path(&self) -> PathBuf {
+        self.config.cache.join("last-request")
+    }
+
+    fn wait_politely(&self) {
+        let last = fs::read_to_string(self.last_request_path()).ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(elapsed) = last.and_then(|last| SystemTime::now().duration_since(last).ok()) {
+            if elapsed < self.config.interval {
+                thread::sleep(self.config.interval - elapsed);
+            }
+        }
+    }
+
+    fn note_request(&self) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        // Forgetting when the last request was only costs some politeness.
+        let _ = write(&self.last_request_path(), &now.as_millis().to_string());
+    }
+}
+
+fn read(url: String, response: Result<ureq::Response, ureq::Error>) -> Result<String, FetchError> {
+    match response {
+        Ok(response) => response.into_string().map_err(|e| FetchError::Transport(format!("{}: {}", url, e))),
+        Err(ureq::Error::Status(code, _)) => Err(FetchError::Status { url, code }),
+        Err(e) => Err(FetchError::Transport(e.to_string())),
+    }
+}
+
+pub fn write(path: &Path, text: &str) -> Result<(), FetchError> {
+    path.parent().map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(path, text))
+        .map_err(|err| FetchError::Io { path: path.to_path_buf(), err })
+}
+
+// A stand-in for the site, to test against: `respond` answers each request
+// it gets, and says what to log of it. Returns where it is, and the log.
+#[cfg(test)]
+pub fn stand_in<T: Send + 'static>(
+    respond: impl Fn(&mut tiny_http::Request) -> (T, tiny_http::ResponseBox) + Send + 'static,
+) -> (String, std::sync::Arc<std::sync::Mutex<Vec<T>>>) {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let log = std::sync::Arc::clone(&seen);
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let (logged, response) = respond(&mut request);
+            log.lock().unwrap().push(logged);
+            let _ = request.respond(response);
+        }
+    });
+    (url, seen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    // The path, session cookie and time of every request the server gets.
+    type Requests = Arc<Mutex<Vec<(String, Option<String>, Instant)>>>;
+
+    // Serving up a day's puzzle and input.
+    fn serve() -> (String, Requests) {
+        stand_in(|request| {
+            let cookie = request.headers().iter()
+                .find(|h| h.field.equiv("Cookie"))
+                .map(|h| h.value.to_string());
+            let path = request.url().to_string();
+            let response = match path.as_str() {
+                "/2018/day/3" => tiny_http::Response::from_string("<main>No Matter How You Slice It</main>"),
+                "/2018/day/3/input" => tiny_http::Response::from_string("#1 @ 1,3: 4x4\n"),
+                _ => tiny_http::Response::from_string("Not Found").with_status_code(404),
+            };
+            ((path, cookie, Instant::now()), response.boxed())
+        })
+    }
+
+    fn config(base_url: &str, session: Option<&str>, test: &str) -> Config {
+        let cache = env::temp_dir().join(format!("aoc-fetch-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&cache);
+        Config {
+            session: session.map(String::from),
+            base_url: base_url.to_string(),
+            interval: Duration::from_millis(200),
+            cache,
+            path: None,
+        }
+    }
+
+    #[test]
+    fn config_file() {
+        let text = "session = \"f00d\"\nbase_url = \"http://localhost:8080\"\ninterval = 0.5\n";
+        let path = Some(PathBuf::from("config.toml"));
+        let config = Config::parse(path.clone(), Some(text), None).unwrap();
+        assert_eq!(config.session.as_deref(), Some("f00d"));
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.interval, Duration::from_millis(500));
+
+        let config = Config::parse(path.clone(), Some(text), Some(String::from("beef\n"))).unwrap();
+        assert_eq!(config.session.as_deref(), Some("beef"));
+        let config = Config::parse(None, None, None).unwrap();
+        assert_eq!((config.session, config.base_url.as_str()), (None, "https://adventofcode.com"));
+        assert!(Config::parse(path, Some("sesion = \"typo\""), None).is_err());
+    }
+
+    #[test]
+    fn fetching() {
+        let (url, seen) = serve();
+        let fetcher = Fetcher::new(config(&url, Some("f00d"), "fetching"));
+
+        assert_eq!(fetcher.input(3).unwrap(), "#1 @ 1,3: 4x4\n");
+        assert_eq!(fetcher.input(3).unwrap(), "#1 @ 1,3: 4x4\n");
+        assert!(fetcher.puzzle(3, false).unwrap().contains("Slice"));
+        assert!(fetcher.puzzle(3, true).unwrap().contains("Slice"));
+        match fetcher.puzzle(25, false) {
+            Err(FetchError::Status { code: 404, .. }) => {},
+            other => panic!("expected a 404, got {:?}", other),
+        }
+
+        // The cached input is only requested once, and every request is
+        // well spaced out, and logged in.
+        let seen = seen.lock().unwrap();
+        let paths = seen.iter().map(|(path, _, _)| path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, ["/2018/day/3/input", "/2018/day/3", "/2018/day/3", "/2018/day/25"]);
+        assert!(seen.iter().all(|(_, cookie, _)| cookie.as_deref() == Some("session=f00d")));
+        for pair in seen.windows(2) {
+            assert!(pair[1].2 - pair[0].2 >= Duration::from_millis(150));
+        }
+        fs::remove_dir_all(&fetcher.config.cache).unwrap();
+    }
+
+    #[test]
+    fn needs_session() {
+        let (url, seen) = serve();
+        let fetcher = Fetcher::new(config(&url, None, "session"));
+        assert!(matches!(fetcher.input(3), Err(FetchError::NoSession { .. })));
+        // Anyone can read the puzzle, though.
+        assert!(fetcher.puzzle(3, false).is_ok());
+        assert_eq!(seen.lock().unwrap()[0].1, None);
+        fs::remove_dir_all(&fetcher.config.cache).unwrap();
+    }
+}
@@ -0,0 +1,650 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc_common::show_answer;
+use aoc_common::diagnostic;
+use aoc_common::input::{self, Source};
+use aoc_common::logger;
+
+use crate::answers::{Answers, Verdict};
+use crate::bench::{Baseline, Change, Sampler, STAGES};
+use crate::days::Puzzle;
+
+mod answers;
+mod bench;
+mod days;
+mod examples;
+mod fetch;
+mod markdown;
+mod report;
+mod scaffold;
+mod submit;
+
+static USAGE: &str = "\
+Usage:
+    aoc run <days> [--part <1|2>] [--input <path|->] [--format <text|json>]
+    aoc bench <days> [--baseline <path>] [--threshold <percent>] [--size <n>]
+    aoc verify [<days>]
+    aoc new <day>
+    aoc fetch <day> [--refresh]
+    aoc examples [<days>]
+    aoc submit <day> <1|2>
+    aoc gen <day> [--seed <n>] [--size <n>]
+
+Days can be a single day (7), an inclusive range (1..14), a comma
+separated list (1,5,9), or `all`. Each day reads its own `days/NN/input`,
+unless a single day is given another input file (or `-` for stdin).
+
+With --format json, each part that's run is reported as a JSON object on a
+line of its own: its answer, how long it (and parsing the input) took, the
+SHA-256 of the input, and any diagnostics - what the day logged, or where
+its input couldn't be parsed. Otherwise what the days log goes to stderr.
+
+Any command can be given --log to see more (or less) of what the days log,
+for every day (--log debug), just some (--log day07=debug,day12=trace), or
+both (--log warn,day07=debug). Only info and above is shown otherwise.
+
+Benchmarks time parsing and both parts separately, and compare them to the
+previous run (kept in target/aoc-bench/baseline.tsv). Anything slower by
+more than the threshold (10% by default) is flagged as a regression. With
+--size, they time made-up inputs of that size instead of the real ones
+(see `aoc gen`), kept separately in the baseline.
+
+Verifying runs every day (or just the given days) against the answers
+recorded in `days/NN/answers.toml`.
+
+New days are set up in `days/NN`, ready for their parts to be filled in.
+Fetching downloads a day's puzzle and input, using the session cookie from
+AOC_SESSION or the config file (see `aoc/src/fetch.rs`), and writes the
+puzzle out as `days/NN/README.md`. Puzzles are cached once fetched, so
+--refresh is needed to see part 2, which is then added to the README.
+
+Examples pulls the worked examples out of each day's README, into test
+fixtures under `days/NN/examples` (answers it couldn't find are left for
+filling in by hand, in `days/NN/examples.toml`).
+
+Submitting sends a part's answer to the site, and records what the site
+made of it in `days/NN/answers.toml`. Answers it turned down (or that it
+would, being higher than one that was too high, say) aren't sent again.
+
+Gen makes up an input for a day and prints it, for trying a day out on more
+than its real input: bigger ones, or lots of different ones. The size is
+counted in whatever makes sense for the day (changes, claims, stars...),
+and is about the size of the real input by default. The same seed (0
+unless given) always makes the same input.";
+
+// Which parts of a day's puzzle should be run.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Parts { Both, One, Two }
+
+impl Parts {
+    fn numbers(self) -> &'static [u8] {
+        match self {
+            Parts::Both => &[1, 2],
+            Parts::One => &[1],
+            Parts::Two => &[2],
+        }
+    }
+}
+
+// How `aoc run` reports the answers: for reading, or for other programs.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Format { Text, Json }
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run { days: Vec<u8>, parts: Parts, input: Source, format: Format },
+    Bench { days: Vec<u8>, baseline: PathBuf, threshold: f64, size: Option<usize> },
+    Verify { days: Vec<u8> },
+    New { day: u8 },
+    Fetch { day: u8, refresh: bool },
+    Examples { days: Vec<u8> },
+    Submit { day: u8, part: u8 },
+    Gen { day: u8, seed: u64, size: Option<usize> },
+}
+
+// Parse a day selection such as "7", "1..14", "3..=5", "1,4,9" or "all".
+fn parse_days(s: &str) -> Result<Vec<u8>, String> {
+    if s == "all" {
+        return Ok(days::all().iter().map(|d| d.day()).collect());
+    }
+
+    let num = |n: &str| n.trim().parse::<u8>()
+        .map_err(|_| format!("not a day number: {:?}", n));
+
+    let mut selected = Vec::new();
+    for item in s.split(',') {
+        if let Some(idx) = item.find("..") {
+            let (lo, hi) = (&item[..idx], item[idx+2..].trim_start_matches('='));
+            let (lo, hi) = (num(lo)?, num(hi)?);
+            if lo > hi {
+                return Err(format!("empty day range: {}", item));
+            }
+            selected.extend(lo..=hi);
+        } else {
+            selected.push(num(item)?);
+        }
+    }
+    Ok(selected)
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    s.parse().map_err(|_| format!("not a day number: {:?}", s))
+}
+
+fn parse_size(s: Option<&String>) -> Result<usize, String> {
+    s.and_then(|s| s.parse().ok()).filter(|&n| n > 0).ok_or_else(|| String::from("--size needs a number, like 1000"))
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+    match args.next().map(|s| s.as_str()) {
+        Some("run") => {
+            let days = parse_days(args.next().ok_or("missing which days to run")?)?;
+            let mut parts = Parts::Both;
+            let mut input = Source::Default;
+            let mut format = Format::Text;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--input" => input = Source::from_arg(Some(args.next().ok_or("--input needs a path")?)),
+                    "--format" => format = match args.next().map(|s| s.as_str()) {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        other => return Err(format!("--format must be text or json, not {:?}", other)),
+                    },
+                    "--part" => parts = match args.next().map(|s| s.as_str()) {
+                        Some("1") => Parts::One,
+                        Some("2") => Parts::Two,
+                        other => return Err(format!("--part must be 1 or 2, not {:?}", other)),
+                    },
+                    other => return Err(format!("unexpected argument: {}", other)),
+                }
+            }
+            if input != Source::Default && days.len() != 1 {
+                return Err(String::from("--input can only be used when running a single day"));
+            }
+            Ok(Command::Run { days, parts, input, format })
+        },
+        Some("bench") => {
+            let days = parse_days(args.next().ok_or("missing which days to benchmark")?)?;
+            let mut baseline = Baseline::default_path();
+            let mut threshold = 10.0;
+            let mut size = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--baseline" => baseline = PathBuf::from(args.next().ok_or("--baseline needs a path")?),
+                    "--threshold" => threshold = args.next().and_then(|t| t.parse().ok())
+                        .filter(|&t: &f64| t >= 0.0)
+                        .ok_or("--threshold needs a percentage, like 10")?,
+                    "--size" => size = Some(parse_size(args.next())?),
+                    other => return Err(format!("unexpected argument: {}", other)),
+                }
+            }
+            Ok(Command::Bench { days, baseline, threshold, size })
+        },
+        Some("verify") => {
+            let days = parse_days(args.next().map_or("all", |s| s.as_str()))?;
+            match args.next() {
+                Some(other) => Err(format!("unexpected argument: {}", other)),
+                None => Ok(Command::Verify { days }),
+            }
+        },
+        Some("new") => {
+            let day = parse_day(args.next().ok_or("missing which day to create")?)?;
+            match args.next() {
+                Some(other) => Err(format!("unexpected argument: {}", other)),
+                None => Ok(Command::New { day }),
+            }
+        },
+        Some("fetch") => {
+            let day = parse_day(args.next().ok_or("missing which day to fetch")?)?;
+            match args.next().map(|s| s.as_str()) {
+                Some("--refresh") => Ok(Command::Fetch { day, refresh: true }),
+                Some(other) => Err(format!("unexpected argument: {}", other)),
+                None => Ok(Command::Fetch { day, refresh: false }),
+            }
+        },
+        Some("examples") => {
+            let days = parse_days(args.next().map_or("all", |s| s.as_str()))?;
+            match args.next() {
+                Some(other) => Err(format!("unexpected argument: {}", other)),
+                None => Ok(Command::Examples { days }),
+            }
+        },
+        Some("submit") => {
+            let day = parse_day(args.next().ok_or("missing which day to submit")?)?;
+            let part = match args.next().map(|s| s.as_str()) {
+                Some("1") => 1,
+                Some("2") => 2,
+                other => return Err(format!("the part to submit must be 1 or 2, not {:?}", other)),
+            };
+            match args.next() {
+                Some(other) => Err(format!("unexpected argument: {}", other)),
+                None => Ok(Command::Submit { day, part }),
+            }
+        },
+        Some("gen") => {
+            let day = parse_day(args.next().ok_or("missing which day to generate an input for")?)?;
+            let (mut seed, mut size) = (0, None);
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--seed" => seed = args.next().and_then(|s| s.parse().ok()).ok_or("--seed needs a number")?,
+                    "--size" => size = Some(parse_size(args.next())?),
+                    other => return Err(format!("unexpected argument: {}", other)),
+                }
+            }
+            Ok(Command::Gen { day, seed, size })
+        },
+        Some(other) => Err(format!("unknown command: {}", other)),
+        None => Err(String::from("missing command")),
+    }
+}
+
+fn find(registry: &[Box<dyn Puzzle>], day: u8) -> Result<&dyn Puzzle, String> {
+    registry.iter().find(|d| d.day() == day).map(|d| &**d)
+        .ok_or_else(|| format!("day {} has not been solved yet", day))
+}
+
+// A day that can't be run doesn't stop the rest from running: it's counted,
+// and the run fails at the end.
+fn run(selected: &[u8], parts: Parts, source: &Source) -> Result<(), String> {
+    let registry = days::all();
+    let (mut failed, mut unanswered) = (0, 0);
+    for &day in selected {
+        println!("Day {:02}", day);
+        let parsed = find(&registry, day).and_then(|puzzle| {
+            let text = input::read(day, source).map_err(|e| e.to_string())?;
+            let input = puzzle.parse(&text)
+                .map_err(|e| diagnostic::render(&*e, &text, &source.name(day)))?;
+            Ok((puzzle, input))
+        });
+        let (puzzle, input) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("  error: {}", e.trim_end());
+                failed += 1;
+                continue;
+            },
+        };
+
+        for &part in parts.numbers() {
+            if !show_answer(&format!("  part{}", part), puzzle.part(part, &*input)) {
+                unanswered += 1;
+            }
+        }
+    }
+    outcome(failed, unanswered)
+}
+
+// How a run went, as a whole.
+fn outcome(failed: usize, unanswered: usize) -> Result<(), String> {
+    match (failed, unanswered) {
+        (0, 0) => Ok(()),
+        (failed, 0) => Err(format!("{} of the days couldn't be run", failed)),
+        (0, unanswered) => Err(format!("{} of the parts had no answer", unanswered)),
+        (failed, unanswered) => Err(format!("{} of the days couldn't be run, and {} of the parts had no answer",
+                                            failed, unanswered)),
+    }
+}
+
+fn run_json(selected: &[u8], parts: Parts, source: &Source) -> Result<(), String> {
+    let registry = days::all();
+    let mut failed = 0;
+    for &day in selected {
+        let read = find(&registry, day)
+            .and_then(|puzzle| Ok((puzzle, input::read(day, source).map_err(|e| e.to_string())?)));
+        let (puzzle, text) = match read {
+            Ok(read) => read,
+            Err(e) => {
+                eprintln!("error: day {}: {}", day, e);
+                failed += 1;
+                continue;
+            },
+        };
+
+        let records = report::run(puzzle, &text, parts.numbers()).unwrap_or_else(|records| {
+            failed += 1;
+            records
+        });
+        for record in &records {
+            println!("{}", serde_json::to_string(record).map_err(|e| e.to_string())?);
+        }
+    }
+    outcome(failed, 0)
+}
+
+fn bench(selected: &[u8], path: &Path, threshold: f64, size: Option<usize>) -> Result<(), String> {
+    let registry = days::all();
+    let mut baseline = Baseline::load(path)
+        .map_err(|e| format!("couldn't read the benchmark baseline: {}", e))?;
+    let sampler = Sampler::default();
+    let mut regressions = 0;
+
+    for &day in selected {
+        let puzzle = find(&registry, day)?;
+        let (text, origin) = match size {
+            Some(size) => (puzzle.generate(0, Some(size)), format!("generated input (size {})", size)),
+            None => (input::read(day, &Source::Default).map_err(|e| e.to_string())?, Source::Default.name(day)),
+        };
+        // Whatever the day logs would be repeated for every sample.
+        logger::capture();
+        let timings = puzzle.bench(&text, &sampler).map_err(|e| diagnostic::render(&*e, &text, &origin))?;
+        logger::take();
+
+        println!("Day {:02}", day);
+        for (stage, stats) in STAGES.iter().zip(&timings) {
+            // Timings for generated inputs are kept apart from the real ones'.
+            let stage = match size {
+                Some(size) => format!("{}@{}", stage, size),
+                None => stage.to_string(),
+            };
+            let change = baseline.compare(day, &stage, stats, threshold);
+            if let Change::Regressed(_) = change {
+                regressions += 1;
+            }
+            println!("  {}: {}  {}", stage, stats, change);
+            baseline.record(day, &stage, stats);
+        }
+    }
+
+    // This run becomes the baseline for the next one, regressions and all.
+    baseline.save(path)
+        .map_err(|e| format!("couldn't save the benchmark baseline to {}: {}", path.display(), e))?;
+    if regressions > 0 {
+        return Err(format!("{} of the timings regressed by more than {}%", regressions, threshold));
+    }
+    Ok(())
+}
+
+fn verify(selected: &[u8]) -> Result<(), String> {
+    let registry = days::all();
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for &day in selected {
+        let puzzle = find(&registry, day)?;
+        let answers = Answers::load(&Answers::path(day))?;
+        println!("Day {:02}", day);
+
+        // Without an input there's nothing to check, but that's no failure.
+        let text = match input::read(day, &Source::Default) {
+            Ok(text) => text,
+            Err(e) => {
+                println!("  missing: {}", e);
+                missing += 2;
+                continue;
+            },
+        };
+        let input = match puzzle.parse(&text) {
+            Ok(input) => input,
+            Err(e) => {
+                print!("  FAILED: {}", diagnostic::render(&*e, &text, &Source::Default.name(day)));
+                failed += 2;
+                continue;
+            },
+        };
+
+        for part in 1..=2 {
+            let actual = match puzzle.part(part, &*input) {
+                Ok(actual) => actual,
+                Err(why) => {
+                    println!("  part{}: FAILED, with no answer ({})", part, why);
+                    failed += 1;
+                    continue;
+                },
+            };
+            let expected = answers.part(part);
+            match answers::check(expected, &actual) {
+                Verdict::Pass => {
+                    println!("  part{}: ok", part);
+                    passed += 1;
+                },
+                Verdict::Missing => {
+                    println!("  part{}: missing (no answer recorded)", part);
+                    missing += 1;
+                },
+                Verdict::Fail => {
+                    println!("  part{}: FAILED", part);
+                    for line in answers::diff(expected.unwrap_or(""), &actual).lines() {
+                        println!("    {}", line);
+                    }
+                    failed += 1;
+                },
+            }
+        }
+    }
+
+    println!("\n{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        return Err(format!("{} of the answers didn't match the registry", failed));
+    }
+    Ok(())
+}
+
+fn new(day: u8) -> Result<(), String> {
+    let root = scaffold::workspace();
+    for path in scaffold::new_day(&root, day)? {
+        println!("created {}", path.strip_prefix(&root).unwrap_or(&path).display());
+    }
+    println!("registered day {} with the runner - try `cargo run -p aoc -- run {}`", day, day);
+    Ok(())
+}
+
+fn fetch(day: u8, refresh: bool) -> Result<(), String> {
+    let fetcher = fetch::Fetcher::new(fetch::Config::load().map_err(|e| e.to_string())?);
+
+    let page = fetcher.puzzle(day, refresh).map_err(|e| e.to_string())?;
+    println!("puzzle -> {}", fetcher.cached(&format!("day{:02}.html", day)).display());
+
+    let path = input::default_path(day);
+    let readme = path.with_file_name("README.md");
+    let desc = markdown::describe(&page, fetcher.base_url())
+        .ok_or_else(|| format!("no puzzle description in {}", fetcher.cached(&format!("day{:02}.html", day)).display()))?;
+    match markdown::merge(fs::read_to_string(&readme).ok().as_deref(), &desc) {
+        Some(text) => {
+            fetch::write(&readme, &text).map_err(|e| e.to_string())?;
+            println!("readme -> {}", readme.display());
+        },
+        None => println!("readme up to date: {}", readme.display()),
+    }
+
+    if path.exists() {
+        println!("input already fetched: {}", path.display());
+    } else {
+        let text = fetcher.input(day).map_err(|e| e.to_string())?;
+        fetch::write(&path, &text).map_err(|e| e.to_string())?;
+        println!("input -> {}", path.display());
+    }
+    Ok(())
+}
+
+fn examples(selected: &[u8]) -> Result<(), String> {
+    let registry = days::all();
+    for &day in selected {
+        let puzzle = find(&registry, day)?;
+        let dir = input::default_path(day).with_file_name("");
+        let fixtures = examples::update(&dir, &|code| puzzle.parse(code).is_ok())?;
+
+        println!("Day {:02}: {} examples", day, fixtures.examples.len());
+        for example in &fixtures.examples {
+            let show = |answer: &Option<String>| match answer {
+                Some(answer) if answer.contains('\n') => String::from("(multi-line)"),
+                Some(answer) => answer.clone(),
+                None => String::from("?"),
+            };
+            println!("  {}: part1 {}, part2 {}", example.input, show(&example.part1), show(&example.part2));
+        }
+    }
+    Ok(())
+}
+
+fn submit(day: u8, part: u8) -> Result<(), String> {
+    let registry = days::all();
+    let puzzle = find(&registry, day)?;
+    let text = input::read(day, &Source::Default).map_err(|e| e.to_string())?;
+    let input = puzzle.parse(&text)
+        .map_err(|e| diagnostic::render(&*e, &text, &Source::Default.name(day)))?;
+    let answer = puzzle.part(part, &*input)
+        .map_err(|why| format!("day {} part {} has no answer to send: {}", day, part, why))?;
+
+    println!("Day {:02} part{}: {}", day, part, answer);
+    let fetcher = fetch::Fetcher::new(fetch::Config::load().map_err(|e| e.to_string())?);
+    match submit::submit(&fetcher, &Answers::path(day), day, part, &answer)? {
+        submit::Outcome::Correct => {
+            println!("  {}", submit::Outcome::Correct);
+            Ok(())
+        },
+        outcome => Err(outcome.to_string()),
+    }
+}
+
+fn gen(day: u8, seed: u64, size: Option<usize>) -> Result<(), String> {
+    let registry = days::all();
+    print!("{}", find(&registry, day)?.generate(seed, size));
+    Ok(())
+}
+
+fn main() {
+    logger::init();
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    let cmd = logger::configure(&mut args).and_then(|_| parse_args(&args)).unwrap_or_else(|e| {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        process::exit(2);
+    });
+    let result = match cmd {
+        Command::Run { days, parts, input, format: Format::Text } => run(&days, parts, &input),
+        Command::Run { days, parts, input, format: Format::Json } => run_json(&days, parts, &input),
+        Command::Bench { days, baseline, threshold, size } => bench(&days, &baseline, threshold, size),
+        Command::Verify { days } => verify(&days),
+        Command::New { day } => new(day),
+        Command::Fetch { day, refresh } => fetch(day, refresh),
+        Command::Examples { days } => examples(&days),
+        Command::Submit { day, part } => submit(day, part),
+        Command::Gen { day, seed, size } => gen(day, seed, size),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e.trim_end());
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn day_selection() {
+        let tests = vec!(
+            ("7",       vec![7]),
+            ("1..4",    vec![1, 2, 3, 4]),
+            ("3..=5",   vec![3, 4, 5]),
+            ("1,5,9",   vec![1, 5, 9]),
+            ("1..2,14", vec![1, 2, 14]),
+        );
+        for (test, expect) in tests {
+            assert_eq!(parse_days(test), Ok(expect));
+        }
+        assert_eq!(parse_days("all").unwrap().len(), days::all().len());
+        assert!(parse_days("5..1").is_err());
+        assert!(parse_days("seven").is_err());
+    }
+
+    #[test]
+    fn run_command() {
+        assert_eq!(parse_args(&args("run all --part 2")),
+                   Ok(Command::Run { days: parse_days("all").unwrap(), parts: Parts::Two,
+                                     input: Source::Default, format: Format::Text }));
+        assert_eq!(parse_args(&args("run 7")),
+                   Ok(Command::Run { days: vec![7], parts: Parts::Both, input: Source::Default,
+                                     format: Format::Text }));
+        assert_eq!(parse_args(&args("run 7 --input -")),
+                   Ok(Command::Run { days: vec![7], parts: Parts::Both, input: Source::Stdin,
+                                     format: Format::Text }));
+        assert_eq!(parse_args(&args("run 1..3 --format json --part 1")),
+                   Ok(Command::Run { days: vec![1, 2, 3], parts: Parts::One, input: Source::Default,
+                                     format: Format::Json }));
+        assert!(parse_args(&args("run 7 --part 3")).is_err());
+        assert!(parse_args(&args("run 7 --format yaml")).is_err());
+        assert!(parse_args(&args("run 1..3 --input big.txt")).is_err());
+        assert!(parse_args(&args("fly 7")).is_err());
+    }
+
+    #[test]
+    fn bench_command() {
+        assert_eq!(parse_args(&args("bench 5,11")),
+                   Ok(Command::Bench { days: vec![5, 11], baseline: Baseline::default_path(),
+                                       threshold: 10.0, size: None }));
+        assert_eq!(parse_args(&args("bench all --baseline old.tsv --threshold 2.5 --size 500")),
+                   Ok(Command::Bench { days: parse_days("all").unwrap(),
+                                       baseline: PathBuf::from("old.tsv"), threshold: 2.5, size: Some(500) }));
+        assert!(parse_args(&args("bench 7 --threshold fast")).is_err());
+        assert!(parse_args(&args("bench 7 --size 0")).is_err());
+        assert!(parse_args(&args("bench 7 --part 1")).is_err());
+    }
+
+    #[test]
+    fn verify_command() {
+        assert_eq!(parse_args(&args("verify")), Ok(Command::Verify { days: parse_days("all").unwrap() }));
+        assert_eq!(parse_args(&args("verify 3..5")), Ok(Command::Verify { days: vec![3, 4, 5] }));
+        assert!(parse_args(&args("verify 3 4")).is_err());
+    }
+
+    #[test]
+    fn new_command() {
+        assert_eq!(parse_args(&args("new 15")), Ok(Command::New { day: 15 }));
+        assert!(parse_args(&args("new")).is_err());
+    }
+
+    #[test]
+    fn fetch_command() {
+        assert_eq!(parse_args(&args("fetch 7")), Ok(Command::Fetch { day: 7, refresh: false }));
+        assert_eq!(parse_args(&args("fetch 7 --refresh")), Ok(Command::Fetch { day: 7, refresh: true }));
+        assert!(parse_args(&args("fetch 1..3")).is_err());
+    }
+
+    #[test]
+    fn examples_command() {
+        assert_eq!(parse_args(&args("examples 6,7")), Ok(Command::Examples { days: vec![6, 7] }));
+        assert_eq!(parse_args(&args("examples")), parse_args(&args("examples all")));
+        assert!(parse_args(&args("examples 6 7")).is_err());
+    }
+
+    #[test]
+    fn submit_command() {
+        assert_eq!(parse_args(&args("submit 3 2")), Ok(Command::Submit { day: 3, part: 2 }));
+        assert!(parse_args(&args("submit 3")).is_err());
+        assert!(parse_args(&args("submit 3 3")).is_err());
+        assert!(parse_args(&args("submit 3..4 1")).is_err());
+    }
+    #[test]
+    fn gen_command() {
+        assert_eq!(parse_args(&args("gen 13")), Ok(Command::Gen { day: 13, seed: 0, size: None }));
+        assert_eq!(parse_args(&args("gen 13 --size 40 --seed 7")), Ok(Command::Gen { day: 13, seed: 7, size: Some(40) }));
+        assert!(parse_args(&args("gen 13 --seed -1")).is_err());
+        assert!(parse_args(&args("gen 1..3")).is_err());
+    }
+
+    #[test]
+    fn runs_every_day() {
+        // Every day is tried, even once one has failed.
+        let missing = Source::File(PathBuf::from("no/such/input"));
+        assert_eq!(run(&[1, 2, 3], Parts::Both, &missing), Err(String::from("3 of the days couldn't be run")));
+        assert_eq!(run_json(&[1, 2], Parts::One, &missing), Err(String::from("2 of the days couldn't be run")));
+        assert_eq!(outcome(1, 2), Err(String::from("1 of the days couldn't be run, and 2 of the parts had no answer")));
+        assert_eq!(outcome(0, 0), Ok(()));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        // Every day's generated input parses, and a seed always makes the same one.
+        for puzzle in days::all() {
+            let text = puzzle.generate(3, None);
+            assert!(puzzle.parse(&text).is_ok(), "day {} generated an input it can't parse", puzzle.day());
+            assert_eq!(puzzle.generate(3, None), text);
+        }
+    }
+}
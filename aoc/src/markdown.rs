@@ -0,0 +1,476 @@
+// Turning a puzzle's page into the day's README. The page is HTML, but only
+// a small, tidy corner of it matters: the `<article class="day-desc">` for
+// each part, and the `day-success` line once both are done. That's read into
+// a little tree of elements, and written back out as markdown - paragraphs,
+// lists, `code`, *emphasis*, links, and fenced blocks for the examples.
+
+// Elements that never have a closing tag.
+const VOID: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Element { name: String, attrs: Vec<(String, String)>, children: Vec<Node> },
+    Text(String),
+}
+
+impl Node {
+    fn attr(&self, key: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } =>
+                attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.attr("class").is_some_and(|c| c.split_whitespace().any(|c| c == class))
+    }
+
+    // All the text inside, with the markup stripped off.
+    fn text(&self) -> String {
+        match self {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => children.iter().map(Node::text).collect(),
+        }
+    }
+
+    // Every element (this one included) that matches `pred`, outermost first,
+    // in document order.
+    fn find<'a>(&'a self, pred: &dyn Fn(&Node) -> bool, found: &mut Vec<&'a Node>) {
+        if pred(self) {
+            found.push(self);
+        } else if let Node::Element { children, .. } = self {
+            for child in children {
+                child.find(pred, found);
+            }
+        }
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out += &rest[..amp];
+        rest = &rest[amp..];
+        let entity = rest.find(';').filter(|&end| end <= 10).map(|end| &rest[1..end]);
+        let decoded = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(std::char::from_u32),
+        });
+        match (entity, decoded) {
+            (Some(entity), Some(c)) => {
+                out.push(c);
+                rest = &rest[entity.len() + 2..];
+            },
+            _ => {
+                out.push('&');
+                rest = &rest[1..];
+            },
+        }
+    }
+    out + rest
+}
+
+fn parse_attrs(mut s: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    loop {
+        s = s.trim_start();
+        let name_end = s.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(s.len());
+        if name_end == 0 {
+            return attrs;
+        }
+        let name = s[..name_end].to_ascii_lowercase();
+        s = s[name_end..].trim_start();
+
+        let value = match s.strip_prefix('=').map(str::trim_start) {
+            Some(rest) => {
+                let (value, after) = match rest.chars().next() {
+                    Some(q) if q == '"' || q == '\'' => {
+                        let end = rest[1..].find(q).map_or(rest.len(), |end| end + 1);
+                        (&rest[1..end], rest.get(end + 1..).unwrap_or(""))
+                    },
+                    _ => {
+                        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                        (&rest[..end], &rest[end..])
+                    },
+                };
+                s = after;
+                decode_entities(value)
+            },
+            None => String::new(),
+        };
+        attrs.push((name, value));
+    }
+}
+
+// Read HTML into a tree. It's forgiving, like a browser: stray closing tags
+// are ignored, and unclosed elements end with whatever contains them.
+fn parse(html: &str) -> Node {
+    // The stack of open elements, each with the children found so far.
+    let mut stack = vec![(String::from("#document"), Vec::new(), Vec::new())];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let lt = rest.find('<').unwrap_or(rest.len());
+        if lt > 0 {
+            stack.last_mut().unwrap().2.push(Node::Text(decode_entities(&rest[..lt])));
+            rest = &rest[lt..];
+            continue;
+        }
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let gt = match rest.find('>') {
+            Some(gt) => gt,
+            None => {
+                stack.last_mut().unwrap().2.push(Node::Text(decode_entities(rest)));
+                break;
+            },
+        };
+        let tag = &rest[1..gt];
+        rest = &rest[gt + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            if let Some(open) = stack.iter().rposition(|(n, _, _)| *n == name) {
+                while stack.len() > open.max(1) {
+                    let (name, attrs, children) = stack.pop().unwrap();
+                    stack.last_mut().unwrap().2.push(Node::Element { name, attrs, children });
+                }
+            }
+            continue;
+        }
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_ascii_lowercase();
+        let attrs = parse_attrs(&tag[name_end..]);
+
+        // Scripts and styles aren't markup, and don't matter to a README.
+        if name == "script" || name == "style" {
+            let close = format!("</{}", name);
+            rest = rest.find(&close).map_or("", |end| &rest[end..]);
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+        if self_closing || VOID.contains(&name.as_str()) {
+            stack.last_mut().unwrap().2.push(Node::Element { name, attrs, children: Vec::new() });
+        } else {
+            stack.push((name, attrs, Vec::new()));
+        }
+    }
+
+    while stack.len() > 1 {
+        let (name, attrs, children) = stack.pop().unwrap();
+        stack.last_mut().unwrap().2.push(Node::Element { name, attrs, children });
+    }
+    let (name, attrs, children) = stack.pop().unwrap();
+    Node::Element { name, attrs, children }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_[]".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// Wrap `text` in enough backticks that none inside can end it early.
+fn fence(text: &str, min: usize) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    "`".repeat(min.max(longest + 1))
+}
+
+// Put the spaces at either end of some marked up text outside of the markup,
+// where markdown expects them: `*a *b` rather than `*a* b`.
+fn wrap(inner: &str, before: &str, after: &str) -> String {
+    let trimmed = inner.trim();
+    if trimmed.is_empty() {
+        return inner.to_string();
+    }
+    let lead = if inner.starts_with(char::is_whitespace) { " " } else { "" };
+    let trail = if inner.ends_with(char::is_whitespace) { " " } else { "" };
+    format!("{}{}{}{}{}", lead, before, trimmed, after, trail)
+}
+
+struct Writer<'a> {
+    base_url: &'a str,
+}
+
+impl<'a> Writer<'a> {
+    fn link(&self, href: &str) -> String {
+        if href.starts_with('/') {
+            format!("{}{}", self.base_url.trim_end_matches('/'), href)
+        } else {
+            href.to_string()
+        }
+    }
+
+    fn inline(&self, nodes: &[Node]) -> String {
+        let mut out = String::new();
+        for node in nodes {
+            let (name, children) = match node {
+                Node::Text(text) => {
+                    // Runs of whitespace are all the same to HTML.
+                    let words = text.split_whitespace().collect::<Vec<_>>();
+                    if text.starts_with(char::is_whitespace) && !words.is_empty() || words.is_empty() && !text.is_empty() {
+                        out.push(' ');
+                    }
+                    out += &escape(&words.join(" "));
+                    if text.ends_with(char::is_whitespace) && !words.is_empty() {
+                        out.push(' ');
+                    }
+                    continue;
+                },
+                Node::Element { name, children, .. } => (name.as_str(), children),
+            };
+            match name {
+                "em" | "strong" | "b" | "i" => out += &wrap(&self.inline(children), "*", "*"),
+                "code" => {
+                    let text = node.text();
+                    let ticks = fence(&text, 1);
+                    let pad = if text.starts_with('`') || text.ends_with('`') { " " } else { "" };
+                    let code = format!("{}{}{}{}{}", ticks, pad, text, pad, ticks);
+                    // Emphasis can't go inside a code span, so it goes around it.
+                    let mut em = Vec::new();
+                    node.find(&|n| matches!(n, Node::Element { name, .. } if name == "em"), &mut em);
+                    if em.is_empty() { out += &code } else { out += &format!("*{}*", code) }
+                },
+                "a" => {
+                    let text = self.inline(children);
+                    match node.attr("href") {
+                        Some(href) => out += &wrap(&text, "[", &format!("]({})", self.link(href))),
+                        None => out += &text,
+                    }
+                },
+                "br" => out += "  \n",
+                _ => out += &self.inline(children),
+            }
+        }
+        out
+    }
+
+    fn block(&self, node: &Node, out: &mut String) {
+        let (name, children) = match node {
+            Node::Text(text) if text.trim().is_empty() => return,
+            Node::Text(_) => return self.paragraph(&self.inline(std::slice::from_ref(node)), out),
+            Node::Element { name, children, .. } => (name.as_str(), children),
+        };
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" =>
+                self.paragraph(&format!("## {}", self.inline(children).trim()), out),
+            "p" => self.paragraph(&self.inline(children), out),
+            "pre" => {
+                let text = node.text();
+                let text = text.trim_end_matches('\n');
+                let ticks = fence(text, 3);
+                *out += &format!("{}\n{}\n{}\n\n", ticks, text, ticks);
+            },
+            "ul" | "ol" => {
+                for (idx, item) in children.iter().filter(|c| matches!(c, Node::Element { name, .. } if name == "li")).enumerate() {
+                    let marker = if name == "ol" { format!("{}.", idx + 1) } else { String::from("-") };
+                    let mut inner = String::new();
+                    if let Node::Element { children, .. } = item {
+                        for child in children {
+                            match child {
+                                Node::Element { name, .. } if name == "ul" || name == "ol" || name == "pre" || name == "p" =>
+                                    self.block(child, &mut inner),
+                                _ => inner += &self.inline(std::slice::from_ref(child)),
+                            }
+                        }
+                    }
+                    let indent = " ".repeat(marker.len() + 1);
+                    let lines = inner.trim().lines()
+                        .enumerate()
+                        .map(|(n, line)| match (n, line.is_empty()) {
+                            (0, _) => format!("{} {}", marker, line),
+                            (_, true) => String::new(),
+                            _ => format!("{}{}", indent, line),
+                        })
+                        .collect::<Vec<_>>();
+                    *out += &lines.join("\n");
+                    out.push('\n');
+                }
+                out.push('\n');
+            },
+            _ => {
+                for child in children {
+                    self.block(child, out);
+                }
+            },
+        }
+    }
+
+    fn paragraph(&self, text: &str, out: &mut String) {
+        let text = text.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+        if !text.is_empty() {
+            *out += &text;
+            *out += "\n\n";
+        }
+    }
+}
+
+// A puzzle page, as markdown: the first part, and the second once it's unlocked.
+#[derive(Debug, PartialEq)]
+pub struct Description {
+    pub part1: String,
+    pub part2: Option<String>,
+}
+
+pub fn describe(html: &str, base_url: &str) -> Option<Description> {
+    let doc = parse(html);
+    let mut found = Vec::new();
+    doc.find(&|n| n.has_class("day-desc") || n.has_class("day-success"), &mut found);
+
+    let writer = Writer{ base_url };
+    let mut parts: Vec<String> = Vec::new();
+    for node in found {
+        let mut md = String::new();
+        writer.block(node, &mut md);
+        // The success line belongs with the part that earned it.
+        match parts.last_mut() {
+            Some(last) if node.has_class("day-success") => last.push_str(&md),
+            _ => parts.push(md),
+        }
+    }
+
+    let mut parts = parts.into_iter().map(|md| md.trim_end().to_string() + "\n");
+    Some(Description{ part1: parts.next()?, part2: parts.next() })
+}
+
+// The text of a page's first `<article>`, which is where the site puts what
+// it has to say - like whether an answer was right.
+pub fn article(html: &str) -> Option<String> {
+    let doc = parse(html);
+    let mut found = Vec::new();
+    doc.find(&|n| matches!(n, Node::Element { name, .. } if name == "article"), &mut found);
+    found.first().map(|article| article.text().split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+// The README to write, given whatever's there now. An existing README keeps
+// its first part (it may have been annotated) and gains the second part when
+// it unlocks. Returns None when there's nothing to change.
+pub fn merge(existing: Option<&str>, desc: &Description) -> Option<String> {
+    let whole = match &desc.part2 {
+        Some(part2) => format!("{}\n{}", desc.part1, part2),
+        None => desc.part1.clone(),
+    };
+    match (existing, &desc.part2) {
+        (None, _) => Some(whole),
+        (Some(readme), Some(part2)) if !readme.contains("Part Two") =>
+            Some(format!("{}\n\n{}", readme.trim_end(), part2)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PAGE: &str = r#"<!DOCTYPE html>
+<html><head><script>if (a < b) { alert("<p>"); }</script></head>
+<body><main>
+<article class="day-desc"><h2>--- Day 5: Alchemical Reduction ---</h2>
+<p>The polymer is formed by smaller <em>units</em> which, when triggered, react.
+For instance, <code>r</code> and <code>R</code> are units of the <a href="/2018/about">same type</a>.</p>
+<ul>
+<li>In <code>aA</code>, <code>a</code> and <code>A</code> react, leaving nothing behind.</li>
+<li>In <code>abAB</code>, <em>no two</em> adjacent units react &amp; <code><em>nothing</em></code> happens.</li>
+</ul>
+<pre><code>dabA<em>cC</em>aCBAcCcaDA  The first 'cC' is removed.
+dabCBAcaDA   No further actions can be taken.
+</code></pre>
+<p>How many units remain after fully reacting the polymer you scanned?</p>
+</article>
+<p>Your puzzle answer was <code>11946</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>What is the length of the <span title="hidden">shortest</span> polymer?</p></article>
+<p>Your puzzle answer was <code>4240</code>.</p><p class="day-success">Both parts of this puzzle are complete! They provide two gold stars: **</p>
+</main></body></html>"#;
+
+    #[test]
+    fn entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &amp;&#39;c&#x27; &bogus; & d"), "a <b> &'c' &bogus; & d");
+    }
+
+    #[test]
+    fn tree() {
+        let doc = parse("<p class='x y'>a<br/><em>b</p>c</i>");
+        let mut found = Vec::new();
+        doc.find(&|n| n.has_class("y"), &mut found);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text(), "ab");
+        assert_eq!(doc.text(), "abc");
+
+        assert_eq!(article("<main><article><p>That's the\n<em>right</em>  answer!</p></article></main>").as_deref(),
+                   Some("That's the right answer!"));
+        assert_eq!(article("<p>Nothing to see</p>"), None);
+    }
+
+    #[test]
+    fn description() {
+        let desc = describe(PAGE, "https://adventofcode.com").unwrap();
+        assert_eq!(desc.part1, "\
+## --- Day 5: Alchemical Reduction ---
+
+The polymer is formed by smaller *units* which, when triggered, react. For instance, `r` and `R` are units of the [same type](https://adventofcode.com/2018/about).
+
+- In `aA`, `a` and `A` react, leaving nothing behind.
+- In `abAB`, *no two* adjacent units react & *`nothing`* happens.
+
+```
+dabAcCaCBAcCcaDA  The first 'cC' is removed.
+dabCBAcaDA   No further actions can be taken.
+```
+
+How many units remain after fully reacting the polymer you scanned?
+");
+        assert_eq!(desc.part2.as_deref(), Some("\
+## --- Part Two ---
+
+What is the length of the shortest polymer?
+
+Both parts of this puzzle are complete! They provide two gold stars: \\*\\*
+"));
+
+        let locked = PAGE.find("<article class=\"day-desc\"><h2 id=\"part2\">").unwrap();
+        assert_eq!(describe(&PAGE[..locked], "").unwrap().part2, None);
+        assert_eq!(describe("<p>Please log in.</p>", ""), None);
+    }
+
+    #[test]
+    fn merging() {
+        let one = Description{ part1: String::from("## Part 1\n"), part2: None };
+        let both = Description{ part1: String::from("## Part 1\n"), part2: Some(String::from("## --- Part Two ---\n")) };
+
+        assert_eq!(merge(None, &one).as_deref(), Some("## Part 1\n"));
+        assert_eq!(merge(None, &both).as_deref(), Some("## Part 1\n\n## --- Part Two ---\n"));
+        assert_eq!(merge(Some("## Part 1\n\nMy notes.\n"), &both).as_deref(),
+                   Some("## Part 1\n\nMy notes.\n\n## --- Part Two ---\n"));
+        assert_eq!(merge(Some("## Part 1\n"), &one), None);
+        assert_eq!(merge(Some("## Part 1\n\n## --- Part Two ---\n"), &both), None);
+    }
+}
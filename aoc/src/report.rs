@@ -0,0 +1,140 @@
+// `aoc run --format json`: a record for each part of each day that's run, one
+// JSON object per line, for other programs to read. Anything the day logged
+// along the way (or what was wrong with its input) comes with it, rather than
+// being printed.
+
+use std::time::Instant;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use aoc_common::NoAnswer;
+use aoc_common::diagnostic::Diagnostic;
+use aoc_common::logger::{self, Entry};
+
+use crate::days::Puzzle;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    // Missing if the input couldn't be parsed, or didn't have an answer.
+    pub answer: Option<String>,
+    // How long the part took to answer, and the input to parse.
+    pub elapsed_ns: Option<u64>,
+    pub parse_ns: u64,
+    // The SHA-256 of the input, so answers from different inputs can't be
+    // mixed up.
+    pub input_hash: String,
+    pub diagnostics: Vec<Note>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Note {
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    // Where in the input a parse error is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl From<Entry> for Note {
+    fn from(entry: Entry) -> Note {
+        Note{ level: entry.level.to_string().to_lowercase(), target: entry.target, message: entry.message,
+              line: None, column: None }
+    }
+}
+
+impl Note {
+    fn parse_error(day: u8, err: &dyn Diagnostic) -> Note {
+        let pos = err.pos();
+        Note{ level: String::from("error"), target: format!("day{:02}", day), message: err.to_string(),
+              line: Some(pos.line), column: Some(pos.column) }
+    }
+
+    fn no_answer(day: u8, why: &NoAnswer) -> Note {
+        Note{ level: String::from("error"), target: format!("day{:02}", day), message: format!("no answer: {}", why),
+              line: None, column: None }
+    }
+}
+
+pub fn input_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn nanos(start: Instant) -> u64 {
+    start.elapsed().as_nanos() as u64
+}
+
+// Run the given parts of a day, with the logger capturing. If the input
+// can't be parsed, every part's record says why (and has no answer).
+pub fn run(puzzle: &dyn Puzzle, text: &str, parts: &[u8]) -> Result<Vec<Record>, Vec<Record>> {
+    let day = puzzle.day();
+    let input_hash = input_hash(text);
+    logger::capture();
+
+    let start = Instant::now();
+    let parsed = puzzle.parse(text);
+    let parse_ns = nanos(start);
+    let mut notes = logger::take().into_iter().map(Note::from).collect::<Vec<_>>();
+
+    let input = match parsed {
+        Ok(input) => input,
+        Err(e) => {
+            return Err(parts.iter().map(|&part| Record{
+                day, part, answer: None, elapsed_ns: None, parse_ns, input_hash: input_hash.clone(),
+                diagnostics: notes.drain(..).chain(Some(Note::parse_error(day, &*e))).collect(),
+            }).collect());
+        },
+    };
+
+    Ok(parts.iter().map(|&part| {
+        let start = Instant::now();
+        let answer = puzzle.part(part, &*input);
+        let elapsed_ns = nanos(start);
+        notes.extend(logger::take().into_iter().map(Note::from));
+        if let Err(why) = &answer {
+            notes.push(Note::no_answer(day, why));
+        }
+        Record{ day, part, answer: answer.ok(), elapsed_ns: Some(elapsed_ns), parse_ns,
+                input_hash: input_hash.clone(), diagnostics: std::mem::take(&mut notes) }
+    }).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::days;
+
+    #[test]
+    fn records() {
+        logger::init();
+        let registry = days::all();
+        let day9 = registry.iter().find(|d| d.day() == 9).unwrap();
+        let records = run(&**day9, "9 players; last marble is worth 25 points\n", &[1]).unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!((record.day, record.part, record.answer.as_deref()), (9, 1, Some("32")));
+        assert_eq!(record.input_hash, input_hash("9 players; last marble is worth 25 points\n"));
+        assert!(record.diagnostics.iter().any(|n| n.message == "players: 9, last_val: 25"));
+
+        let json = serde_json::to_value(record).unwrap();
+        assert_eq!(json["answer"], "32");
+        assert!(json["elapsed_ns"].is_u64() && json["diagnostics"][0].get("line").is_none());
+
+        let records = run(&**day9, "9 players; last marble is worth lots of points", &[1, 2]).unwrap_err();
+        assert_eq!(records.iter().map(|r| r.part).collect::<Vec<_>>(), [1, 2]);
+        let note = &records[1].diagnostics[0];
+        assert_eq!((note.level.as_str(), note.line, note.column), ("error", Some(1), Some(33)));
+        assert!(records.iter().all(|r| r.answer.is_none()));
+    }
+
+    #[test]
+    fn hashes() {
+        assert_eq!(input_hash(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    }
+}
@@ -0,0 +1,197 @@
+// Setting up a new day: a crate under `days/NN` laid out like all the others
+// (parse, the two parts, the example tests, and a thin `main`), which is
+// then registered with the runner so `aoc run NN` picks it up - and given a
+// fuzz target, if the workspace fuzzes its days.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+static TEMPLATES: [(&str, &str); 3] = [
+    ("Cargo.toml", include_str!("../templates/day/Cargo.toml.tmpl")),
+    ("src/lib.rs", include_str!("../templates/day/lib.rs.tmpl")),
+    ("src/main.rs", include_str!("../templates/day/main.rs.tmpl")),
+];
+
+static FUZZ_TARGET: &str = include_str!("../templates/fuzz/target.rs.tmpl");
+
+// The root of the workspace this runner was built in.
+pub fn workspace() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), ".."].iter().collect()
+}
+
+fn render(template: &str, day: u8) -> String {
+    template.replace("{{NN}}", &format!("{:02}", day)).replace("{{DAY}}", &day.to_string())
+}
+
+// Put `line` into the sorted run of lines starting with `prefix`, keeping it
+// sorted. Days are zero padded, so sorting the text sorts the days.
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Result<String, String> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let run = lines.iter().enumerate()
+        .filter(|(_, l)| l.trim_start().starts_with(prefix))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    let last = *run.last().ok_or_else(|| format!("no `{}` lines to add to", prefix))?;
+    let idx = run.into_iter().find(|&idx| lines[idx] > line).unwrap_or(last + 1);
+
+    let mut out = lines[..idx].to_vec();
+    out.push(line);
+    out.extend(&lines[idx..]);
+    Ok(out.join("\n") + "\n")
+}
+
+// Add `line` to the file at `path`, unless it's already there.
+fn register(path: &Path, prefix: &str, line: &str) -> Result<(), String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+    if text.lines().any(|l| l == line) {
+        return Ok(());
+    }
+    let text = insert_sorted(&text, prefix, line).map_err(|e| format!("{}: {}", path.display(), e))?;
+    fs::write(path, text).map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+}
+
+// Create day `day`'s crate in the workspace at `root`, returning the files
+// that were written. The day's directory may already hold an input or a
+// README, but not a crate.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there's no day {} in the advent calendar", day));
+    }
+    let dir = root.join("days").join(format!("{:02}", day));
+    if dir.join("Cargo.toml").exists() {
+        return Err(format!("day {} already exists in {}", day, dir.display()));
+    }
+
+    let mut created = Vec::new();
+    for (name, template) in TEMPLATES.iter() {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap_or(&dir))
+            .and_then(|_| fs::write(&path, render(template, day)))
+            .map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+        created.push(path);
+    }
+
+    // The workspace picks up every `days/*` crate by itself, but the runner
+    // needs to depend on the new one and list it.
+    register(&root.join("aoc").join("Cargo.toml"), "aoc-2018-",
+             &format!("aoc-2018-{:02} = {{ path = \"../days/{:02}\" }}", day, day))?;
+    register(&root.join("aoc").join("src").join("days.rs"), "puzzle::<aoc_2018_",
+             &format!("        puzzle::<aoc_2018_{:02}::Day{:02}>(),", day, day))?;
+
+    let fuzz = root.join("fuzz");
+    if fuzz.join("Cargo.toml").exists() {
+        created.push(new_fuzz_target(&fuzz, day)?);
+    }
+    Ok(created)
+}
+
+// Give day `day` a target in the fuzzing crate at `fuzz`: the target itself,
+// the dependency on the day, and the `[[bin]]` for it.
+fn new_fuzz_target(fuzz: &Path, day: u8) -> Result<PathBuf, String> {
+    let target = fuzz.join("fuzz_targets").join(format!("day{:02}.rs", day));
+    fs::create_dir_all(fuzz.join("fuzz_targets"))
+        .and_then(|_| fs::write(&target, render(FUZZ_TARGET, day)))
+        .map_err(|e| format!("couldn't write {}: {}", target.display(), e))?;
+
+    let manifest = fuzz.join("Cargo.toml");
+    register(&manifest, "aoc-2018-", &format!("aoc-2018-{:02} = {{ path = \"../days/{:02}\" }}", day, day))?;
+    let text = fs::read_to_string(&manifest).map_err(|e| format!("couldn't read {}: {}", manifest.display(), e))?;
+    if !text.lines().any(|l| l == format!("name = \"day{:02}\"", day)) {
+        let bin = format!("\n[[bin]]\nname = \"day{:02}\"\npath = \"fuzz_targets/day{:02}.rs\"\ntest = false\ndoc = false\n", day, day);
+        fs::write(&manifest, text + &bin).map_err(|e| format!("couldn't write {}: {}", manifest.display(), e))?;
+    }
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates() {
+        let lib = render(TEMPLATES[1].1, 7);
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(!lib.contains("{{"));
+    }
+
+    #[test]
+    fn sorted_registration() {
+        let text = "[dependencies]\naoc-2018-01 = 1\naoc-2018-03 = 3\n\n[other]\n";
+        assert_eq!(insert_sorted(text, "aoc-2018-", "aoc-2018-02 = 2").unwrap(),
+                   "[dependencies]\naoc-2018-01 = 1\naoc-2018-02 = 2\naoc-2018-03 = 3\n\n[other]\n");
+        assert_eq!(insert_sorted(text, "aoc-2018-", "aoc-2018-15 = 15").unwrap(),
+                   "[dependencies]\naoc-2018-01 = 1\naoc-2018-03 = 3\naoc-2018-15 = 15\n\n[other]\n");
+        assert!(insert_sorted("[dependencies]\n", "aoc-2018-", "aoc-2018-15 = 15").is_err());
+    }
+
+    #[test]
+    fn scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"),
+                  "[dependencies]\naoc-2018-14 = { path = \"../days/14\" }\n").unwrap();
+        fs::write(root.join("aoc/src/days.rs"),
+                  "    vec![\n        puzzle::<aoc_2018_14::Day14>(),\n    ]\n").unwrap();
+
+        let created = new_day(&root, 15).unwrap();
+        assert_eq!(created.len(), 3);
+        assert!(fs::read_to_string(root.join("days/15/src/main.rs")).unwrap().contains("Day15"));
+        assert!(fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap()
+                .ends_with("aoc-2018-15 = { path = \"../days/15\" }\n"));
+        assert!(fs::read_to_string(root.join("aoc/src/days.rs")).unwrap()
+                .contains("Day14>(),\n        puzzle::<aoc_2018_15::Day15>(),\n    ]"));
+
+        assert!(new_day(&root, 15).is_err());
+        assert!(new_day(&root, 26).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    // A new day's tests should pass as they are, before it has examples or
+    // answers of its own - so build one, against the real `aoc-common`, and
+    // run them.
+    #[cfg(unix)]
+    #[test]
+    fn scaffolded_tests() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-tests-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), "[dependencies]\naoc-2018-14 = { path = \"../days/14\" }\n").unwrap();
+        fs::write(root.join("aoc/src/days.rs"), "        puzzle::<aoc_2018_14::Day14>(),\n").unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"days/15\"]\n").unwrap();
+        // The same versions of everything, when there's a lock file to say which.
+        let _ = fs::copy(workspace().join("Cargo.lock"), root.join("Cargo.lock"));
+        std::os::unix::fs::symlink(workspace().join("common"), root.join("common")).unwrap();
+        new_day(&root, 15).unwrap();
+
+        // Built away from the workspace's own target directory, which the
+        // `cargo test` running this has locked.
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let output = std::process::Command::new(cargo)
+            .args(["test", "--offline", "-q", "-p", "aoc-2018-15"])
+            .current_dir(&root)
+            .env("CARGO_TARGET_DIR", workspace().join("target").join("scaffold"))
+            .output().unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert!(output.status.success(), "{}{}",
+                String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    fn fuzz_targets() {
+        let root = std::env::temp_dir().join(format!("aoc-fuzz-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::create_dir_all(root.join("fuzz")).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), "[dependencies]\naoc-2018-14 = { path = \"../days/14\" }\n").unwrap();
+        fs::write(root.join("aoc/src/days.rs"), "        puzzle::<aoc_2018_14::Day14>(),\n").unwrap();
+        fs::write(root.join("fuzz/Cargo.toml"), "[dependencies]\naoc-2018-14 = { path = \"../days/14\" }\n\n\
+                                                 [[bin]]\nname = \"day14\"\npath = \"fuzz_targets/day14.rs\"\n").unwrap();
+
+        let created = new_day(&root, 15).unwrap();
+        assert_eq!(created.last(), Some(&root.join("fuzz/fuzz_targets/day15.rs")));
+        assert!(fs::read_to_string(root.join("fuzz/fuzz_targets/day15.rs")).unwrap().contains("aoc_2018_15::Day15"));
+        let manifest = fs::read_to_string(root.join("fuzz/Cargo.toml")).unwrap();
+        assert!(manifest.contains("aoc-2018-14 = { path = \"../days/14\" }\naoc-2018-15 = { path = \"../days/15\" }\n"));
+        assert!(manifest.ends_with("[[bin]]\nname = \"day15\"\npath = \"fuzz_targets/day15.rs\"\ntest = false\ndoc = false\n"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
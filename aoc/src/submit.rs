@@ -0,0 +1,175 @@
+// Sending an answer to the site, and making sense of what it says back.
+// Every outcome worth remembering goes into the day's answers registry: the
+// right answer, so it can be verified from then on, and wrong ones, so they
+// aren't sent again (see `answers::Answers::vet`).
+
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answers::{self, Answers, Hint};
+use crate::fetch::Fetcher;
+use crate::markdown;
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong(Option<Hint>),
+    // Answers are rate limited, more so after a few wrong ones.
+    Wait(Duration),
+    // The part was already solved, or isn't unlocked yet.
+    WrongLevel,
+    // Anything else the site has to say.
+    Unknown(String),
+}
+
+impl Outcome {
+    pub fn parse(page: &str) -> Outcome {
+        let text = match markdown::article(page) {
+            Some(text) => text,
+            None => return Outcome::Unknown(page.split_whitespace().collect::<Vec<_>>().join(" ")),
+        };
+
+        if text.starts_with("That's the right answer") {
+            Outcome::Correct
+        } else if text.starts_with("That's not the right answer") {
+            if text.contains("answer is too high") {
+                Outcome::Wrong(Some(Hint::TooHigh))
+            } else if text.contains("answer is too low") {
+                Outcome::Wrong(Some(Hint::TooLow))
+            } else {
+                Outcome::Wrong(None)
+            }
+        } else if text.starts_with("You gave an answer too recently") {
+            Outcome::Wait(left_to_wait(&text).unwrap_or_else(|| Duration::from_secs(60)))
+        } else if text.starts_with("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown(text)
+        }
+    }
+}
+
+// How long "You have 1m 30s left to wait" says to wait.
+fn left_to_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    text[start..end].split_whitespace().try_fold(Duration::from_secs(0), |total, amount| {
+        let (n, unit) = amount.split_at(amount.len().checked_sub(1)?);
+        let secs = match unit {
+            "h" => 60 * 60,
+            "m" => 60,
+            "s" => 1,
+            _ => return None,
+        };
+        Some(total + Duration::from_secs(n.parse::<u64>().ok()? * secs))
+    })
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "that's the right answer!"),
+            Outcome::Wrong(None) => write!(f, "that's not the right answer"),
+            Outcome::Wrong(Some(hint)) => write!(f, "that's not the right answer: it's {}", hint),
+            Outcome::Wait(left) => write!(f, "an answer was sent too recently; try again in {}s", left.as_secs()),
+            Outcome::WrongLevel => write!(f, "that part is either solved already, or still locked"),
+            Outcome::Unknown(text) => write!(f, "the site said: {}", text),
+        }
+    }
+}
+
+// Submit an answer for one part of a day, unless the registry at `registry`
+// says it's pointless, and record what came of it.
+pub fn submit(fetcher: &Fetcher, registry: &Path, day: u8, part: u8, answer: &str) -> Result<Outcome, String> {
+    if answer.contains('\n') {
+        return Err(String::from("answers spanning several lines have to be read, and sent by hand"));
+    }
+    Answers::load(registry)?.vet(part, answer)?;
+
+    let page = fetcher.submit(day, part, answer).map_err(|e| e.to_string())?;
+    let outcome = Outcome::parse(&page);
+    match outcome {
+        Outcome::Correct => answers::record(registry, part, answer)?,
+        Outcome::Wrong(hint) => answers::record_wrong(registry, part, answer, hint)?,
+        _ => {},
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+
+    use crate::fetch::{stand_in, Config};
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>", text)
+    }
+
+    #[test]
+    fn outcomes() {
+        assert_eq!(Outcome::parse(&page("That's the right answer! You are <span class=\"day-success\">one gold star</span> closer.")),
+                   Outcome::Correct);
+        assert_eq!(Outcome::parse(&page("That's not the right answer; your answer is too high. If you're stuck, ...")),
+                   Outcome::Wrong(Some(Hint::TooHigh)));
+        assert_eq!(Outcome::parse(&page("That's not the right answer. If you're stuck, make sure ...")),
+                   Outcome::Wrong(None));
+        assert_eq!(Outcome::parse(&page("You gave an answer too recently; you have to wait after submitting an \
+                                         answer before trying again.  You have 2m 14s left to wait.")),
+                   Outcome::Wait(Duration::from_secs(134)));
+        assert_eq!(Outcome::parse(&page("You don't seem to be solving the right level.  Did you already complete it?")),
+                   Outcome::WrongLevel);
+        assert_eq!(Outcome::parse("<p>Puzzle inputs differ by user.</p>"),
+                   Outcome::Unknown(String::from("<p>Puzzle inputs differ by user.</p>")));
+    }
+
+    // A stand-in for the site, which only takes 42 for an answer (and thinks
+    // everything else is too high), logging each answer it's sent.
+    fn serve() -> (String, Arc<Mutex<Vec<String>>>) {
+        stand_in(|request| {
+            let mut form = String::new();
+            request.as_reader().read_to_string(&mut form).unwrap();
+            let logged_in = request.headers().iter().any(|h| h.field.equiv("Cookie") && h.value == "session=f00d");
+            let text = match (request.method(), request.url(), form.as_str()) {
+                (tiny_http::Method::Post, "/2018/day/1/answer", _) if !logged_in => "Not logged in.",
+                (tiny_http::Method::Post, "/2018/day/1/answer", "level=1&answer=42") => "That's the right answer!",
+                (tiny_http::Method::Post, "/2018/day/1/answer", _) =>
+                    "That's not the right answer; your answer is too high.",
+                _ => "Puzzle inputs differ by user.",
+            };
+            (form, tiny_http::Response::from_string(page(text)).boxed())
+        })
+    }
+
+    #[test]
+    fn submitting() {
+        let (url, seen) = serve();
+        let cache = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let registry = cache.join("answers.toml");
+        fs::create_dir_all(&cache).unwrap();
+        let fetcher = Fetcher::new(Config {
+            session: Some(String::from("f00d")),
+            base_url: url,
+            interval: Duration::from_millis(10),
+            cache: cache.clone(),
+            path: None,
+        });
+
+        assert_eq!(submit(&fetcher, &registry, 1, 1, "50"), Ok(Outcome::Wrong(Some(Hint::TooHigh))));
+        // Neither the same answer, nor a higher one, is sent again.
+        assert!(submit(&fetcher, &registry, 1, 1, "50").is_err());
+        assert!(submit(&fetcher, &registry, 1, 1, "51").is_err());
+        assert_eq!(submit(&fetcher, &registry, 1, 1, "42"), Ok(Outcome::Correct));
+        assert!(submit(&fetcher, &registry, 1, 1, "42").is_err());
+        assert!(submit(&fetcher, &registry, 1, 2, "#  #\n####").is_err());
+        assert_eq!(*seen.lock().unwrap(), ["level=1&answer=50", "level=1&answer=42"]);
+
+        let answers = Answers::load(&registry).unwrap();
+        assert_eq!(answers.part(1), Some("42"));
+        assert_eq!(answers.wrong.len(), 1);
+        fs::remove_dir_all(&cache).unwrap();
+    }
+}
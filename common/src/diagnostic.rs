@@ -0,0 +1,127 @@
+// Parse errors that point at the exact spot in the puzzle input where things
+// went wrong, and a way to show that spot to whoever's running the puzzle.
+
+use std::error::Error;
+use std::fmt;
+
+// A spot in the puzzle input, counted from 1 - just like an editor does.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Pos {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Pos {
+    pub fn new(line: usize, column: usize) -> Pos {
+        Pos{ line, column }
+    }
+
+    // Where `token` sits, given it was sliced out of `line` (the `line_idx`th
+    // line, counting from 0 as `enumerate` does).
+    pub fn of(line_idx: usize, line: &str, token: &str) -> Pos {
+        Pos::new(line_idx + 1, column_of(line, token))
+    }
+
+    // Where the byte `offset` of the whole puzzle input falls.
+    pub fn at(puzzle: &str, offset: usize) -> Pos {
+        let before = puzzle.get(..offset).unwrap_or(puzzle);
+        let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+        Pos::new(before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+    }
+
+    // Where `token` sits, given it was sliced out of the whole `puzzle` input.
+    pub fn within(puzzle: &str, token: &str) -> Pos {
+        Pos::at(puzzle, (token.as_ptr() as usize).wrapping_sub(puzzle.as_ptr() as usize))
+    }
+
+    // The spot just past the end of the puzzle input, for when it runs out early.
+    pub fn end_of(puzzle: &str) -> Pos {
+        match puzzle.trim_end().lines().enumerate().last() {
+            Some((idx, line)) => Pos::new(idx + 1, line.chars().count() + 1),
+            None => Pos::new(1, 1),
+        }
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+// The (1-based, character) column `token` starts at, where `token` is a slice
+// of `line`. Tokens from somewhere else just point at the start of the line.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize).wrapping_sub(start);
+    match line.get(..offset) {
+        Some(prefix) => prefix.chars().count() + 1,
+        None => 1,
+    }
+}
+
+// Every day's parse error knows where it happened.
+pub trait Diagnostic: Error {
+    fn pos(&self) -> Pos;
+}
+
+// Render a parse error the way rustc would, quoting the offending line of the
+// puzzle input (named by `origin`) and pointing out the column.
+pub fn render(err: &dyn Diagnostic, puzzle: &str, origin: &str) -> String {
+    let pos = err.pos();
+    let mut out = format!("{}\n --> {}:{}:{}\n", err, origin, pos.line, pos.column);
+
+    if let Some(line) = puzzle.lines().nth(pos.line.saturating_sub(1)) {
+        let gutter = pos.line.to_string();
+        let pad = " ".repeat(gutter.len());
+        out += &format!("{} |\n{} | {}\n{} | {}^\n",
+                        pad, gutter, line, pad, " ".repeat(pos.column.saturating_sub(1)));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Oops(Pos);
+
+    impl fmt::Display for Oops {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}: expected `,`", self.0)
+        }
+    }
+    impl Error for Oops {}
+    impl Diagnostic for Oops {
+        fn pos(&self) -> Pos { self.0 }
+    }
+
+    #[test]
+    fn token_positions() {
+        let line = "#1 @ 1,3: 4x4";
+        let token = line.split(' ').nth(2).unwrap();
+        assert_eq!(Pos::of(4, line, token), Pos::new(5, 6));
+        assert_eq!(column_of(line, &String::from("elsewhere")), 1);
+
+        assert_eq!(Pos::at("1 2\n3 4\n", 2), Pos::new(1, 3));
+        assert_eq!(Pos::at("1 2\n3 4\n", 6), Pos::new(2, 3));
+        let puzzle = "1 2\n3 4\n";
+        assert_eq!(Pos::within(puzzle, &puzzle[6..]), Pos::new(2, 3));
+        assert_eq!(Pos::end_of(puzzle), Pos::new(2, 4));
+        assert_eq!(Pos::end_of(""), Pos::new(1, 1));
+    }
+
+    #[test]
+    fn rendering() {
+        let puzzle = "1, 1\n1 6\n8, 3";
+        let expect = "\
+line 2, column 2: expected `,`
+ --> input:2:2
+  |
+2 | 1 6
+  |  ^
+";
+        assert_eq!(render(&Oops(Pos::new(2, 2)), puzzle, "input"), expect);
+    }
+}
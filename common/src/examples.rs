@@ -0,0 +1,179 @@
+// The worked examples from each day's README, kept as test fixtures in the
+// day's directory: the inputs under `examples/`, and what each part should
+// make of them in `examples.toml`:
+//
+//     [[example]]
+//     input = "examples/1.txt"
+//     part1 = "17"
+//     part2 = "16"
+//
+// `aoc examples` pulls them out of the README, and `examples!` tests a day
+// against every one of them.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{normalize, Answer, Solution};
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Example {
+    // Relative to the day's directory.
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct Examples {
+    #[serde(default, rename = "example")]
+    pub examples: Vec<Example>,
+}
+
+impl Examples {
+    // Read a day's examples. A day without any just hasn't had them pulled
+    // out of its README yet.
+    pub fn load(dir: &Path) -> Result<Examples, String> {
+        let path = dir.join("examples.toml");
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Examples::default()),
+            Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e)),
+        };
+        toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    // Written by hand rather than by `toml`, so multi-line answers (day 10's
+    // message) stay readable.
+    pub fn to_toml(&self) -> String {
+        let quote = |s: &str| if s.contains('\n') && !s.contains("'''") {
+            format!("'''\n{}'''", s)
+        } else {
+            toml::Value::String(s.to_string()).to_string()
+        };
+
+        let mut out = String::from("# Worked examples from README.md, checked by each day's tests.\n");
+        for example in &self.examples {
+            out += &format!("\n[[example]]\ninput = {}\n", quote(&example.input));
+            for (part, answer) in [("part1", &example.part1), ("part2", &example.part2)].iter() {
+                if let Some(answer) = answer {
+                    out += &format!("{} = {}\n", part, quote(answer));
+                }
+            }
+        }
+        out
+    }
+}
+
+// Run a day against every example in `dir`, returning how many answers were
+// checked, or a description of every one that was wrong.
+pub fn check<S: Solution>(dir: &Path, config: &S::Config) -> Result<usize, String> {
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for example in Examples::load(dir)?.examples {
+        let path = dir.join(&example.input);
+        let puzzle = fs::read_to_string(&path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        let input = match S::parse(&puzzle) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(format!("{}: {}", example.input, e));
+                continue;
+            },
+        };
+
+        let show = |answer: Answer<String>| answer.unwrap_or_else(|why| format!("no answer ({})", why));
+        let answers = [
+            (1, &example.part1, example.part1.as_ref().map(|_| show(S::part1(&input, config).map(|a| a.to_string())))),
+            (2, &example.part2, example.part2.as_ref().map(|_| show(S::part2(&input, config).map(|a| a.to_string())))),
+        ];
+        for (part, expected, actual) in answers.iter() {
+            if let (Some(expected), Some(actual)) = (expected, actual) {
+                checked += 1;
+                if normalize(expected) != normalize(actual) {
+                    failures.push(format!("{}: part {} should be `{}`, but was `{}`",
+                                          example.input, part, expected, actual));
+                }
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(checked)
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+// What `examples!` tests: every example a day has, of which there has to be
+// at least one answer to check.
+pub fn test<S: Solution>(dir: &Path, config: &S::Config) {
+    let path = dir.join("examples.toml");
+    if !path.exists() {
+        panic!("no {} (pull the examples out of the README with `aoc examples {}`, \
+                or say there are none yet with `examples!(Day{:02}, none_yet)`)", path.display(), S::DAY, S::DAY);
+    }
+    match check::<S>(dir, config) {
+        Ok(0) => panic!("no example answers in {}", path.display()),
+        Ok(_) => {},
+        Err(e) => panic!("{}", e),
+    }
+}
+
+// What `examples!(DayNN, none_yet)` tests: that there really are none yet, so
+// that once there are, they're tested.
+pub fn none_yet<S: Solution>(dir: &Path) {
+    let path = dir.join("examples.toml");
+    if path.exists() {
+        panic!("there are examples in {} now, so test them with `examples!(Day{:02})`", path.display(), S::DAY);
+    }
+}
+
+// A test that runs a day's parts against all of its examples, with the given
+// config (or the default one):
+//
+//     aoc_common::examples!(Day07, Flags{ nworkers: 2, time_offset: 0 });
+//
+// A new day, that hasn't had its examples pulled out of its README yet, says
+// so instead:
+//
+//     aoc_common::examples!(Day15, none_yet);
+#[macro_export]
+macro_rules! examples {
+    ($day:ty) => {
+        $crate::examples!($day, Default::default());
+    };
+    ($day:ty, none_yet) => {
+        #[test]
+        fn examples() {
+            $crate::examples::none_yet::<$day>(::std::path::Path::new(env!("CARGO_MANIFEST_DIR")));
+        }
+    };
+    ($day:ty, $config:expr) => {
+        #[test]
+        fn examples() {
+            $crate::examples::test::<$day>(::std::path::Path::new(env!("CARGO_MANIFEST_DIR")), &$config);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixtures() {
+        let examples = Examples{ examples: vec![
+            Example{ input: String::from("examples/1.txt"), part1: Some(String::from("17")), part2: None },
+            Example{ input: String::from("examples/2.txt"), part1: None, part2: Some(String::from("#  #\n####\n")) },
+        ]};
+        let text = examples.to_toml();
+        assert!(text.contains("\n[[example]]\ninput = \"examples/1.txt\"\npart1 = \"17\"\n"));
+        assert!(text.ends_with("part2 = '''\n#  #\n####\n'''\n"));
+        assert_eq!(toml::from_str::<Examples>(&text).unwrap(), examples);
+
+        assert_eq!(Examples::load(Path::new("no/such/day")).unwrap(), Examples::default());
+    }
+}
@@ -0,0 +1,142 @@
+// Made-up puzzle inputs, for trying the days out on more than their one real
+// input and handful of examples: bigger ones, to see how the solutions scale,
+// and lots of different ones, to shake out the edge cases.
+//
+// Inputs are generated from a seed, so the same seed always makes the same
+// input - on any machine, and with any version of the dependencies, which is
+// why there's a little random number generator here rather than `rand`.
+
+use crate::Solution;
+
+// SplitMix64: tiny, fast, and plenty random enough for puzzle inputs.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng{ state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // A number from `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "nothing below 0 to choose from");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    // A number from `lo..=hi`.
+    pub fn between(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi, "nothing between {} and {}", lo, hi);
+        let span = (hi as i128 - lo as i128 + 1) as u128;
+        (lo as i128 + ((self.next_u64() as u128 * span) >> 64) as i128) as i64
+    }
+
+    // True, `p` of the time.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// A day that can make up its own inputs.
+pub trait Generate: Solution {
+    // How big an input is by default: about as big as the real one.
+    const SIZE: usize;
+
+    // A valid input of about `size` - lines, claims, stars, whatever makes
+    // sense for the day - which has answers to both parts.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+// Generate a day's input, as `aoc gen` does.
+pub fn generate<S: Generate>(seed: u64, size: usize) -> String {
+    S::generate(&mut Rng::new(seed), size)
+}
+
+// Parsed inputs for property tests: one each of `size` from the first
+// `count` seeds, along with the seed (to say which one, if a test fails).
+pub fn inputs<S: Generate>(count: u64, size: usize) -> impl Iterator<Item = (u64, S::Input)> {
+    (0..count).map(move |seed| {
+        let puzzle = generate::<S>(seed, size);
+        match S::parse(&puzzle) {
+            Ok(input) => (seed, input),
+            Err(e) => panic!("seed {}: {}\n{}", seed, crate::diagnostic::render(&e, &puzzle, "generated"), puzzle),
+        }
+    })
+}
+
+// Parse and solve generated inputs of a day (of `size`, from a few seeds),
+// to check that its generator only makes inputs with answers:
+//
+//     aoc_common::generated!(Day07, 10);
+#[macro_export]
+macro_rules! generated {
+    ($day:ty, $size:expr) => {
+        $crate::generated!($day, $size, Default::default());
+    };
+    ($day:ty, $size:expr, $config:expr) => {
+        #[test]
+        fn generated() {
+            use $crate::Solution;
+            for (seed, input) in $crate::generate::inputs::<$day>(4, $size) {
+                if let Err(why) = <$day>::part1(&input, &$config) {
+                    panic!("seed {}: no answer to part 1 ({})", seed, why);
+                }
+                if let Err(why) = <$day>::part2(&input, &$config) {
+                    panic!("seed {}: no answer to part 2 ({})", seed, why);
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeatable() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        assert_eq!((0..10).map(|_| a.next_u64()).collect::<Vec<_>>(), (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+        // The stream is part of the contract: a seed has to keep making the same input.
+        assert_eq!(Rng::new(0).next_u64(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!(rng.below(3) < 3);
+            assert!((-2..=2).contains(&rng.between(-2, 2)));
+        }
+        assert_eq!(rng.between(i64::MIN, i64::MIN), i64::MIN);
+        let _ = rng.between(i64::MIN, i64::MAX);
+        assert!(!rng.chance(0.0) && rng.chance(1.0));
+
+        let mut items = (0..20).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+        assert!(items.contains(rng.pick(&items)));
+    }
+}
@@ -0,0 +1,238 @@
+// A rectangle of cells, addressed by `(x, y)` from the top left. Plenty of
+// puzzles are played out on one - fabric, maps of tracks, the sky at night -
+// and they're often read from a map of characters, and shown as one again.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::diagnostic::{Diagnostic, Pos};
+
+// The offsets to a cell's neighbors: the first four share an edge with it,
+// and the rest only a corner.
+const NEIGHBORS: [(isize, isize); 8] = [(0, -1), (1, 0), (0, 1), (-1, 0), (1, -1), (1, 1), (-1, 1), (-1, -1)];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // Row by row.
+    cells: Vec<T>,
+}
+
+// What went wrong reading a grid from a map, where `E` is what's wrong with
+// a single character.
+#[derive(Debug, PartialEq)]
+pub enum GridError<E> {
+    Cell(E),
+    // A grid is a rectangle, so every line is as wide as the first.
+    Ragged { pos: Pos, expected: usize, found: usize },
+}
+
+impl<E: fmt::Display> fmt::Display for GridError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Cell(e) => e.fmt(f),
+            GridError::Ragged { pos, expected, found } =>
+                write!(f, "{}: expected a line {} wide, found {}", pos, expected, found),
+        }
+    }
+}
+
+impl<E: Diagnostic> Error for GridError<E> {}
+
+impl<E: Diagnostic> Diagnostic for GridError<E> {
+    fn pos(&self) -> Pos {
+        match self {
+            GridError::Cell(e) => e.pos(),
+            GridError::Ragged { pos, .. } => *pos,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid{ width, height, cells: vec![fill; width * height] }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| cell(x, y)).collect();
+        Grid{ width, height, cells }
+    }
+
+    // Read a grid from a map, one character per cell, and one line per row.
+    // `cell` turns each character into a cell, given its position.
+    pub fn parse<E>(map: &str, mut cell: impl FnMut(char, usize, usize) -> Result<T, E>)
+        -> Result<Grid<T>, GridError<E>>
+    {
+        let mut cells = Vec::new();
+        let (mut width, mut height) = (0, 0);
+        for (y, line) in map.lines().enumerate() {
+            let start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(cell(c, x, y).map_err(GridError::Cell)?);
+            }
+            let found = cells.len() - start;
+            if y == 0 {
+                width = found;
+            } else if found != width {
+                return Err(GridError::Ragged { pos: Pos::new(y + 1, found.min(width) + 1),
+                                               expected: width, found });
+            }
+            height += 1;
+        }
+        Ok(Grid{ width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if self.contains((x, y)) { self.cells.get(y * self.width + x) } else { None }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if self.contains((x, y)) { self.cells.get_mut(y * self.width + x) } else { None }
+    }
+
+    // Whether a cell is on the outside edge of the grid.
+    pub fn is_edge(&self, (x, y): (usize, usize)) -> bool {
+        x == 0 || y == 0 || x + 1 == self.width || y + 1 == self.height
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {} is outside of a grid {} high", y, self.height);
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` can't take a width of 0, but then there's nothing to see.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside of a grid {} wide", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    // Every cell, along with where it is, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(idx, cell)| ((idx % width, idx / width), cell))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    fn around(&self, (x, y): (usize, usize), offsets: &'static [(isize, isize)])
+        -> impl Iterator<Item = (usize, usize)>
+    {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let (nx, ny) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            if nx < width && ny < height { Some((nx, ny)) } else { None }
+        })
+    }
+
+    // The (up to) four cells sharing an edge with a cell: up, right, down
+    // and left.
+    pub fn neighbors4(&self, at: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.around(at, &NEIGHBORS[..4])
+    }
+
+    // The (up to) eight cells touching a cell, corners included.
+    pub fn neighbors8(&self, at: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.around(at, &NEIGHBORS)
+    }
+
+    // Draw the grid as text, one character per cell.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        self.rows().map(|row| row.iter().map(&mut cell).collect::<String>()).collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get((x, y)).unwrap_or_else(|| panic!("{},{} is outside of a {}x{} grid", x, y, width, height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut((x, y)).unwrap_or_else(|| panic!("{},{} is outside of a {}x{} grid", x, y, width, height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct BadCell(char, usize, usize);
+
+    fn bits(map: &str) -> Result<Grid<bool>, GridError<BadCell>> {
+        Grid::parse(map, |c, x, y| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(BadCell(c, x, y)),
+        })
+    }
+
+    #[test]
+    fn maps() {
+        let grid = bits("#..\n.#.\n..#\n#..").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert!(grid[(2, 2)] && !grid[(2, 1)]);
+        assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), "#..\n.#.\n..#\n#..");
+
+        assert_eq!(bits("#.\n#x"), Err(GridError::Cell(BadCell('x', 1, 1))));
+        assert_eq!(bits("#..\n#.\n"), Err(GridError::Ragged { pos: Pos::new(2, 3), expected: 3, found: 2 }));
+        assert_eq!(bits("").unwrap().render(|_| '#'), "");
+    }
+
+    #[test]
+    fn bounds() {
+        let mut grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+        assert_eq!(grid.get((2, 1)), Some(&12));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        grid[(1, 1)] = 99;
+        assert_eq!(grid.row(1), [10, 99, 12]);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), [&1, &99]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.cells().nth(4), Some(((1, 1), &99)));
+        assert!(grid.is_edge((0, 1)) && grid.is_edge((1, 0)));
+        assert!(!Grid::new(3, 3, 0).is_edge((1, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "2,0 is outside of a 2x2 grid")]
+    fn outside() {
+        let _ = Grid::new(2, 2, 0)[(2, 0)];
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(grid.neighbors4((1, 1)).collect::<Vec<_>>(), [(1, 0), (2, 1), (1, 2), (0, 1)]);
+        assert_eq!(grid.neighbors4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).collect::<Vec<_>>(), [(2, 1), (1, 2), (1, 1)]);
+    }
+}
@@ -0,0 +1,265 @@
+// Priority queues that pop the smallest item first. The standard library's
+// `BinaryHeap` pops the largest, so scheduling by earliest time or lowest
+// letter otherwise means a newtype with a reversed `Ord` for every type.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::iter::FromIterator;
+
+#[derive(Clone)]
+pub struct MinHeap<T: Ord> {
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> MinHeap<T> {
+    pub fn new() -> MinHeap<T> {
+        MinHeap{ heap: BinaryHeap::new() }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.heap.push(Reverse(item));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|Reverse(item)| item)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|Reverse(item)| item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    // Everything still queued, smallest first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap.into_sorted_vec().into_iter().rev().map(|Reverse(item)| item).collect()
+    }
+}
+
+impl<T: Ord> Default for MinHeap<T> {
+    fn default() -> MinHeap<T> {
+        MinHeap::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> MinHeap<T> {
+        MinHeap{ heap: items.into_iter().map(Reverse).collect() }
+    }
+}
+
+impl<T: Ord> Extend<T> for MinHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.heap.extend(items.into_iter().map(Reverse));
+    }
+}
+
+impl<T: Ord + fmt::Debug> fmt::Debug for MinHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.heap.iter().map(|Reverse(item)| item)).finish()
+    }
+}
+
+// Names an item pushed onto an `IndexedHeap`, for as long as it's there.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle(usize);
+
+struct Entry<P, T> {
+    priority: P,
+    handle: Handle,
+    value: T,
+}
+
+// A min-heap of values, each queued at a priority that can be lowered
+// (Dijkstra's decrease-key) or taken off the queue early, via the handle
+// it's pushed with. Values with the same priority come out in the order they
+// were pushed.
+pub struct IndexedHeap<P: Ord, T> {
+    entries: Vec<Entry<P, T>>,
+    // Where each handle's entry is in `entries`, if it's still queued.
+    slots: Vec<Option<usize>>,
+}
+
+impl<P: Ord, T> IndexedHeap<P, T> {
+    pub fn new() -> IndexedHeap<P, T> {
+        IndexedHeap{ entries: Vec::new(), slots: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn push(&mut self, priority: P, value: T) -> Handle {
+        let handle = Handle(self.slots.len());
+        self.slots.push(Some(self.entries.len()));
+        self.entries.push(Entry{ priority, handle, value });
+        self.sift_up(self.entries.len() - 1);
+        handle
+    }
+
+    pub fn peek(&self) -> Option<(&P, &T)> {
+        self.entries.first().map(|e| (&e.priority, &e.value))
+    }
+
+    pub fn pop(&mut self) -> Option<(P, T)> {
+        let handle = self.entries.first()?.handle;
+        self.remove(handle)
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.slot(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<(&P, &T)> {
+        self.slot(handle).map(|idx| (&self.entries[idx].priority, &self.entries[idx].value))
+    }
+
+    // Lower an item's priority, if it's still queued and the new one is
+    // lower. Returns whether it was.
+    pub fn decrease(&mut self, handle: Handle, priority: P) -> bool {
+        match self.slot(handle) {
+            Some(idx) if priority < self.entries[idx].priority => {
+                self.entries[idx].priority = priority;
+                self.sift_up(idx);
+                true
+            },
+            _ => false,
+        }
+    }
+
+    // Take an item off the queue, wherever it is in it.
+    pub fn remove(&mut self, handle: Handle) -> Option<(P, T)> {
+        let idx = self.slots.get_mut(handle.0)?.take()?;
+        let entry = self.entries.swap_remove(idx);
+        if idx < self.entries.len() {
+            self.slots[self.entries[idx].handle.0] = Some(idx);
+            self.sift_down(idx);
+            self.sift_up(idx);
+        }
+        Some((entry.priority, entry.value))
+    }
+
+    fn slot(&self, handle: Handle) -> Option<usize> {
+        self.slots.get(handle.0).copied().flatten()
+    }
+
+    fn less(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.entries[a], &self.entries[b]);
+        a.priority.cmp(&b.priority).then(a.handle.cmp(&b.handle)) == Ordering::Less
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.entries.swap(a, b);
+        self.slots[self.entries[a].handle.0] = Some(a);
+        self.slots[self.entries[b].handle.0] = Some(b);
+    }
+
+    fn sift_up(&mut self, mut idx: usize) {
+        while idx > 0 {
+            let parent = (idx - 1) / 2;
+            if !self.less(idx, parent) {
+                break;
+            }
+            self.swap(idx, parent);
+            idx = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut idx: usize) {
+        loop {
+            let mut least = idx;
+            for child in [2 * idx + 1, 2 * idx + 2].iter().copied() {
+                if child < self.entries.len() && self.less(child, least) {
+                    least = child;
+                }
+            }
+            if least == idx {
+                break;
+            }
+            self.swap(idx, least);
+            idx = least;
+        }
+    }
+}
+
+impl<P: Ord, T> Default for IndexedHeap<P, T> {
+    fn default() -> IndexedHeap<P, T> {
+        IndexedHeap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_first() {
+        let mut heap = "CABFDE".chars().collect::<MinHeap<_>>();
+        assert_eq!(heap.peek(), Some(&'A'));
+        assert_eq!(heap.pop(), Some('A'));
+        heap.extend(vec!['G', 'A']);
+        assert_eq!(heap.len(), 7);
+        assert_eq!(heap.into_sorted_vec(), ['A', 'B', 'C', 'D', 'E', 'F', 'G']);
+
+        let mut jobs = MinHeap::new();
+        jobs.push((61, 'C'));
+        jobs.push((3, 'Z'));
+        jobs.push((3, 'B'));
+        assert_eq!((jobs.pop(), jobs.pop(), jobs.pop(), jobs.pop()), (Some((3, 'B')), Some((3, 'Z')), Some((61, 'C')), None));
+        assert!(jobs.is_empty());
+    }
+
+    #[test]
+    fn handles() {
+        let mut heap = IndexedHeap::new();
+        let a = heap.push(10, 'a');
+        let b = heap.push(20, 'b');
+        let c = heap.push(30, 'c');
+        let d = heap.push(20, 'd');
+
+        assert!(heap.decrease(c, 5));
+        assert!(!heap.decrease(a, 15));
+        assert_eq!(heap.get(c), Some((&5, &'c')));
+        assert_eq!(heap.remove(a), Some((10, 'a')));
+        assert_eq!(heap.remove(a), None);
+        assert!(!heap.contains(a) && !heap.decrease(a, 0));
+
+        // Ties go in the order they were pushed.
+        assert_eq!(heap.peek(), Some((&5, &'c')));
+        let popped = std::iter::from_fn(|| heap.pop()).collect::<Vec<_>>();
+        assert_eq!(popped, [(5, 'c'), (20, 'b'), (20, 'd')]);
+        assert!(!heap.contains(b) && !heap.contains(d) && heap.is_empty());
+    }
+
+    #[test]
+    fn against_sorting() {
+        // A scrambled, repeatable sequence of priorities, some of them
+        // lowered or removed along the way.
+        let priorities = (0..200u32).map(|n| n * 7919 % 211).collect::<Vec<_>>();
+        let mut heap = IndexedHeap::new();
+        let handles = priorities.iter().enumerate().map(|(n, &p)| heap.push(p, n)).collect::<Vec<_>>();
+        let mut expected = priorities.iter().enumerate().map(|(n, &p)| (p, n)).collect::<Vec<_>>();
+        for n in (0..200).step_by(3) {
+            heap.decrease(handles[n], expected[n].0 / 2);
+            expected[n].0 /= 2;
+        }
+        for n in (1..200).step_by(5) {
+            assert_eq!(heap.remove(handles[n]), Some(expected[n]));
+        }
+        expected = expected.into_iter().enumerate().filter(|(n, _)| n % 5 != 1).map(|(_, e)| e).collect();
+        expected.sort();
+
+        assert_eq!(std::iter::from_fn(|| heap.pop()).collect::<Vec<_>>(), expected);
+    }
+}
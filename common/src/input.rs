@@ -0,0 +1,107 @@
+// Finding and reading puzzle input at runtime - from a file, from stdin, or
+// from the day's own `days/NN/input` when nothing else is given.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Default,
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    // Interpret a command line argument: `-` is stdin, anything else is a
+    // path, and no argument at all means the day's default input.
+    pub fn from_arg(arg: Option<&str>) -> Source {
+        match arg {
+            None => Source::Default,
+            Some("-") => Source::Stdin,
+            Some(path) => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    // What to call the input in messages.
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            Source::Default => default_path(day).display().to_string(),
+            Source::Stdin => String::from("<stdin>"),
+            Source::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+// Where a day's puzzle input lives, when it's been fetched into the repo.
+pub fn default_path(day: u8) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "days", &format!("{:02}", day), "input"]
+        .iter().collect()
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    day: u8,
+    source: Source,
+    err: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "couldn't read day {} input from {}: {}",
+               self.day, self.source.name(self.day), self.err)?;
+        if self.source == Source::Default && self.err.kind() == io::ErrorKind::NotFound {
+            write!(f, " (fetch it with `aoc fetch {}`, or pass an input path)", self.day)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.err)
+    }
+}
+
+pub fn read(day: u8, source: &Source) -> Result<String, InputError> {
+    let result = match source {
+        Source::Default => fs::read_to_string(default_path(day)),
+        Source::File(path) => fs::read_to_string(path),
+        Source::Stdin => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf).map(|_| buf)
+        },
+    };
+    result.map_err(|err| InputError{ day, source: source.clone(), err })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources() {
+        assert_eq!(Source::from_arg(None), Source::Default);
+        assert_eq!(Source::from_arg(Some("-")), Source::Stdin);
+        assert_eq!(Source::from_arg(Some("days/01/input")),
+                   Source::File(PathBuf::from("days/01/input")));
+    }
+
+    #[test]
+    fn default_input() {
+        let puzzle = read(1, &Source::Default).unwrap();
+        assert!(!puzzle.is_empty());
+    }
+
+    #[test]
+    fn missing_input() {
+        let err = read(25, &Source::Default).unwrap_err();
+        assert_eq!(err.err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().starts_with("couldn't read day 25 input from"));
+
+        let err = read(1, &Source::File(PathBuf::from("no/such/input"))).unwrap_err();
+        assert!(err.to_string().contains("no/such/input"));
+    }
+}
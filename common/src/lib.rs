@@ -0,0 +1,153 @@
+// Shared pieces used by every day's puzzle crate, and the `aoc` runner.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::process;
+
+pub mod diagnostic;
+pub mod examples;
+pub mod generate;
+pub mod grid;
+pub mod heap;
+pub mod input;
+pub mod logger;
+pub mod parse;
+pub mod point;
+
+use crate::diagnostic::Diagnostic;
+
+// Every day's puzzle has the same shape: parse the input once, then answer
+// two questions about it. Implementing this lets the runner (and anything
+// else, like benchmarks) treat all of the days the same.
+pub trait Solution {
+    // Which day of December the puzzle was released.
+    const DAY: u8;
+
+    // The puzzle input, once it's been read into something useful.
+    // It owns all of its data, so it can be handed around freely.
+    type Input: 'static;
+    // Extra knobs for a puzzle - worker counts, thresholds, debug flags.
+    // The default is whatever the real puzzle asks for, and days without
+    // any knobs use `()`.
+    type Config: Default;
+
+    type Answer1: Display;
+    type Answer2: Display;
+
+    // What went wrong reading a malformed puzzle input.
+    type Error: Diagnostic + 'static;
+
+    fn parse(puzzle: &str) -> Result<Self::Input, Self::Error>;
+    fn part1(input: &Self::Input, config: &Self::Config) -> Answer<Self::Answer1>;
+    fn part2(input: &Self::Input, config: &Self::Config) -> Answer<Self::Answer2>;
+}
+
+// A part's answer - or why there isn't one. Some inputs are well formed, but
+// have nothing to find: no frequency ever repeats, no carts ever crash.
+pub type Answer<T> = Result<T, NoAnswer>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoAnswer(String);
+
+impl NoAnswer {
+    pub fn new(why: impl Into<String>) -> NoAnswer {
+        NoAnswer(why.into())
+    }
+}
+
+impl fmt::Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for NoAnswer {}
+
+// The entry point for each day's own binary. The first argument that isn't a
+// `--flag` picks the puzzle input (see `input::Source`), and `--log` what's
+// logged along the way (see `logger::Filter`):
+//
+//     cargo run -p aoc-2018-07 -- [path/to/input | -] [--log day07=debug]
+pub fn main<S: Solution>(config: &S::Config) {
+    logger::init();
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Err(e) = logger::configure(&mut args) {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    }
+    let arg = args.into_iter().find(|arg| arg == "-" || !arg.starts_with("--"));
+    let source = input::Source::from_arg(arg.as_deref());
+    let puzzle = input::read(S::DAY, &source).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    });
+    match solve::<S>(&puzzle, config) {
+        Ok(true) => {},
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprint!("error: {}", diagnostic::render(&e, &puzzle, &source.name(S::DAY)));
+            process::exit(1);
+        },
+    }
+}
+
+// The value of a `--flag <value>` in a day's own arguments, taken out of them,
+// for days that take more than the puzzle input.
+pub fn flag(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    match args.iter().position(|arg| arg == name) {
+        Some(idx) => args.drain(idx..(idx + 2).min(args.len())).nth(1)
+            .map(Some).ok_or_else(|| format!("{} needs a value", name)),
+        None => Ok(None),
+    }
+}
+
+// Solve both parts of a puzzle, printing out the answers. Returns whether
+// both parts had one.
+pub fn solve<S: Solution>(puzzle: &str, config: &S::Config) -> Result<bool, S::Error> {
+    let input = S::parse(puzzle)?;
+    let one = show_answer("part1", S::part1(&input, config));
+    let two = show_answer("part2", S::part2(&input, config));
+    Ok(one && two)
+}
+
+// An answer, line by line, for comparing with another. Multi-line answers
+// (like day 10's message) are compared without any trailing whitespace, which
+// editors love to strip out of the files answers are kept in.
+pub fn normalize(answer: &str) -> Vec<&str> {
+    answer.trim_end().lines().map(str::trim_end).collect()
+}
+
+// Print out the answer to a part, under `label`. Answers like day 10's
+// message span multiple lines, so give them room. With no answer, why not
+// goes to stderr, as every error does. Returns whether there was an answer.
+pub fn show_answer(label: &str, answer: Answer<impl Display>) -> bool {
+    match answer.map(|answer| answer.to_string()) {
+        Ok(answer) if answer.contains('\n') => println!("{}:\n{}", label, answer),
+        Ok(answer) => println!("{}: {}", label, answer),
+        Err(why) => {
+            eprintln!("{}: no answer ({})", label, why);
+            return false;
+        },
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags() {
+        let mut args = ["in.txt", "--passes", "3", "--format"].iter().map(|arg| arg.to_string()).collect();
+        assert_eq!(flag(&mut args, "--passes"), Ok(Some(String::from("3"))));
+        assert_eq!(flag(&mut args, "--passes"), Ok(None));
+        assert_eq!(flag(&mut args, "--format"), Err(String::from("--format needs a value")));
+        assert_eq!(args, ["in.txt"]);
+    }
+
+    #[test]
+    fn normalized() {
+        assert_eq!(normalize("#  #\n####\n"), normalize("#  #  \n####"));
+        assert_ne!(normalize("17"), normalize("16"));
+    }
+}
@@ -0,0 +1,183 @@
+// Where the puzzles' progress chatter goes. Days report it with the `log`
+// macros (`log::info!("Growth stabilized...")`), which keeps it out of the
+// answers on stdout: it's written to stderr, or captured, for the runner to
+// put alongside the answers it reports.
+//
+// Only `info` and up is shown unless asked for, so days can leave their
+// debugging in (`log::debug!("In-Degree: {:?}", indegrees)`) and have it
+// switched on from the command line instead: `--log day07=debug`.
+
+use std::borrow::Cow;
+use std::io::Write;
+use std::sync::{Mutex, RwLock};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+// One message that was logged.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+// How much to log: a level for every day, a level for one day (`day07=debug`),
+// or a comma separated mix of them (`warn,day07=trace,day12=debug`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    default: LevelFilter,
+    days: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    const DEFAULT: Filter = Filter{ default: LevelFilter::Info, days: Vec::new() };
+
+    pub fn parse(spec: &str) -> Result<Filter, String> {
+        let level = |s: &str| s.parse::<LevelFilter>()
+            .map_err(|_| format!("not a log level (off, error, warn, info, debug or trace): {:?}", s));
+
+        let mut filter = Filter::DEFAULT;
+        for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match item.find('=') {
+                Some(idx) => {
+                    let day = item[..idx].trim_start_matches("day").parse::<u8>()
+                        .map_err(|_| format!("expected a day to log, like day07, not {:?}", &item[..idx]))?;
+                    filter.days.push((format!("day{:02}", day), level(&item[idx+1..])?));
+                },
+                None => filter.default = level(item)?,
+            }
+        }
+        Ok(filter)
+    }
+
+    fn level(&self, target: &str) -> LevelFilter {
+        // Later settings for a day win, as they would on the command line.
+        self.days.iter().rev().find(|(day, _)| day == target).map_or(self.default, |&(_, level)| level)
+    }
+
+    fn max(&self) -> LevelFilter {
+        self.days.iter().map(|&(_, level)| level).fold(self.default, Ord::max)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        Filter::DEFAULT
+    }
+}
+
+// What a day's messages are logged under: `day07`, rather than the module
+// they came from (`aoc_2018_07::...`).
+pub fn target(module: &str) -> Cow<'_, str> {
+    let krate = module.split("::").next().unwrap_or(module);
+    match krate.strip_prefix("aoc_2018_").and_then(|day| day.parse::<u8>().ok()) {
+        Some(day) => Cow::Owned(format!("day{:02}", day)),
+        None => Cow::Borrowed(krate),
+    }
+}
+
+struct Logger {
+    filter: RwLock<Filter>,
+    // While capturing, everything logged is kept here instead of printed.
+    captured: Mutex<Option<Vec<Entry>>>,
+}
+
+static LOGGER: Logger = Logger{ filter: RwLock::new(Filter::DEFAULT), captured: Mutex::new(None) };
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let filter = self.filter.read().unwrap_or_else(|e| e.into_inner());
+        metadata.level() <= filter.level(&target(metadata.target()))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let entry = Entry{ level: record.level(), target: target(record.target()).into_owned(),
+                           message: record.args().to_string() };
+        match self.captured.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            Some(entries) => entries.push(entry),
+            None => {
+                let _ = writeln!(std::io::stderr(), "[{} {}] {}", entry.level, entry.target, entry.message);
+            },
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+// Start listening to the `log` macros. It's fine to call this more than once.
+pub fn init() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(LOGGER.filter.read().unwrap_or_else(|e| e.into_inner()).max());
+    }
+}
+
+pub fn set_filter(filter: Filter) {
+    log::set_max_level(filter.max());
+    *LOGGER.filter.write().unwrap_or_else(|e| e.into_inner()) = filter;
+}
+
+// Pull any `--log <filter>` out of the command line arguments, and log with
+// the (last) filter given.
+pub fn configure(args: &mut Vec<String>) -> Result<(), String> {
+    let mut filter = None;
+    while let Some(idx) = args.iter().position(|arg| arg == "--log") {
+        let spec = args.drain(idx..(idx + 2).min(args.len())).nth(1)
+            .ok_or("--log needs a filter, like day07=debug")?;
+        filter = Some(Filter::parse(&spec)?);
+    }
+    if let Some(filter) = filter {
+        set_filter(filter);
+    }
+    Ok(())
+}
+
+// Keep everything logged from now on, until it's `take`n, rather than
+// printing it.
+pub fn capture() {
+    LOGGER.captured.lock().unwrap_or_else(|e| e.into_inner()).get_or_insert_with(Vec::new);
+}
+
+// Everything logged since the last time, while capturing.
+pub fn take() -> Vec<Entry> {
+    LOGGER.captured.lock().unwrap_or_else(|e| e.into_inner()).as_mut().map(std::mem::take).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capturing() {
+        init();
+        capture();
+        log::info!(target: "aoc_2018_09", "players: {}", 10);
+        log::trace!("too quiet to hear");
+        assert_eq!(take(), [Entry{ level: Level::Info, target: String::from("day09"),
+                                   message: String::from("players: 10") }]);
+        assert_eq!(take(), []);
+    }
+
+    #[test]
+    fn filters() {
+        let filter = Filter::parse("warn, day7=trace,day12=debug,day07=debug").unwrap();
+        assert_eq!(filter.level("day07"), LevelFilter::Debug);
+        assert_eq!(filter.level("day12"), LevelFilter::Debug);
+        assert_eq!(filter.level("day13"), LevelFilter::Warn);
+        assert_eq!(filter.max(), LevelFilter::Trace);
+        assert_eq!(Filter::parse("").unwrap(), Filter::default());
+        assert!(Filter::parse("day07=loud").is_err());
+        assert!(Filter::parse("aoc=debug").is_err());
+
+        assert_eq!(target("aoc_2018_07::assemble"), "day07");
+        assert_eq!(target("aoc"), "aoc");
+
+        let mut args = ["run", "--log", "day07=debug", "7"].iter().map(|s| s.to_string()).collect();
+        configure(&mut args).unwrap();
+        assert_eq!(args, ["run", "7"]);
+        assert!(configure(&mut vec![String::from("--log")]).is_err());
+        assert!(configure(&mut vec![String::from("--log"), String::from("day07")]).is_err());
+    }
+}
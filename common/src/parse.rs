@@ -0,0 +1,334 @@
+// A small kit for reading puzzle inputs, one piece at a time, without regexes
+// and without copying: everything read is a slice of the puzzle itself.
+//
+// A `Cursor` walks through the input. Each piece it reads either comes back,
+// or the reason it couldn't be read does: a `Fail` naming the spot, what was
+// expected there, and what was found instead. Days turn those into their own
+// `ParseError`s (usually with `From`, so `?` does the work):
+//
+//     // #12 @ 369,930: 21x14
+//     parse::records(puzzle, |line| {
+//         line.literal("#")?;
+//         let id = line.int()?;
+//         line.literal(" @ ")?;
+//         ...
+//     })
+
+use std::error::Error;
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
+
+use crate::diagnostic::{Diagnostic, Pos};
+
+// What a parser was looking for when it failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Expected {
+    // Some fixed text, like ` => `.
+    Literal(&'static str),
+    // An integer, maybe signed: `42`, `+7`, `-3`.
+    Number,
+    // An integer, which was there, but too large (or small) for its type.
+    InRange,
+    // Anything else, described for a person: "a step from `A` to `Z`".
+    Thing(&'static str),
+    // Nothing more, other than whitespace.
+    End,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Literal(lit) => write!(f, "`{}`", lit),
+            Expected::Number => write!(f, "a number"),
+            Expected::InRange => write!(f, "a number in range"),
+            Expected::Thing(thing) => write!(f, "{}", thing),
+            Expected::End => write!(f, "the end of the line"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fail<'a> {
+    pub pos: Pos,
+    pub expected: Expected,
+    // What was there instead: the rest of the line for text that didn't
+    // match, or just the offending token for everything else.
+    pub found: &'a str,
+    // The whole line it happened on.
+    pub line: &'a str,
+}
+
+impl<'a> Fail<'a> {
+    fn new(puzzle: &'a str, at: &'a str, expected: Expected, found: &'a str) -> Fail<'a> {
+        let offset = (at.as_ptr() as usize).wrapping_sub(puzzle.as_ptr() as usize).min(puzzle.len());
+        let start = puzzle[..offset].rfind('\n').map_or(0, |n| n + 1);
+        let end = puzzle[offset..].find('\n').map_or(puzzle.len(), |n| offset + n);
+        Fail{ pos: Pos::within(puzzle, at), expected, found, line: puzzle[start..end].trim_end() }
+    }
+}
+
+impl<'a> fmt::Display for Fail<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: expected {}, found `{}`", self.pos, self.expected, self.found)
+    }
+}
+
+impl<'a> Error for Fail<'a> {}
+
+impl<'a> Diagnostic for Fail<'a> {
+    fn pos(&self) -> Pos {
+        self.pos
+    }
+}
+
+// The rest of the line `s` starts on.
+fn rest_of_line(s: &str) -> &str {
+    s[..s.find('\n').unwrap_or(s.len())].trim_end()
+}
+
+// The token `s` starts with, for saying what was found: a run of the things
+// numbers and names are made of, or failing that, a single character.
+fn token(s: &str) -> &str {
+    let end = s.find(|c: char| !(c.is_alphanumeric() || "+-._".contains(c))).unwrap_or(s.len());
+    match (end, s.chars().next()) {
+        (0, Some(c)) => &s[..c.len_utf8()],
+        _ => &s[..end],
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Cursor<'a> {
+    // The whole input, so failures can say where they happened.
+    puzzle: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(puzzle: &'a str) -> Cursor<'a> {
+        Cursor{ puzzle, rest: puzzle }
+    }
+
+    // What's left to read.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    pub fn pos(&self) -> Pos {
+        Pos::within(self.puzzle, self.rest)
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    // Fail here, finding the token at the cursor.
+    pub fn fail(&self, expected: Expected) -> Fail<'a> {
+        Fail::new(self.puzzle, self.rest, expected, token(self.rest))
+    }
+
+    fn advance(&mut self, n: usize) -> Cursor<'a> {
+        let (taken, rest) = self.rest.split_at(n);
+        self.rest = rest;
+        Cursor{ puzzle: self.puzzle, rest: taken }
+    }
+
+    // Read `lit`, if it's next.
+    pub fn eat(&mut self, lit: &str) -> bool {
+        if self.rest.starts_with(lit) {
+            self.advance(lit.len());
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn literal(&mut self, lit: &'static str) -> Result<(), Fail<'a>> {
+        if self.eat(lit) {
+            Ok(())
+        } else {
+            Err(Fail::new(self.puzzle, self.rest, Expected::Literal(lit), rest_of_line(self.rest)))
+        }
+    }
+
+    // Read characters for as long as they match, as a cursor of their own.
+    pub fn take_while(&mut self, mut pred: impl FnMut(char) -> bool) -> Cursor<'a> {
+        let end = self.rest.find(|c| !pred(c)).unwrap_or(self.rest.len());
+        self.advance(end)
+    }
+
+    pub fn skip_while(&mut self, pred: impl FnMut(char) -> bool) {
+        self.take_while(pred);
+    }
+
+    // Read one character, which has to be `expected`.
+    pub fn char_if(&mut self, expected: &'static str, pred: impl FnOnce(char) -> bool) -> Result<char, Fail<'a>> {
+        match self.peek() {
+            Some(c) if pred(c) => {
+                self.advance(c.len_utf8());
+                Ok(c)
+            },
+            _ => Err(self.fail(Expected::Thing(expected))),
+        }
+    }
+
+    // Read an integer: an optional sign, then digits.
+    pub fn int<T: FromStr<Err = ParseIntError>>(&mut self) -> Result<T, Fail<'a>> {
+        let sign = if self.rest.starts_with(['+', '-']) { 1 } else { 0 };
+        let end = self.rest[sign..].find(|c: char| !c.is_ascii_digit()).map_or(self.rest.len(), |n| sign + n);
+        if end == sign {
+            return Err(self.fail(Expected::Number));
+        }
+        match self.rest[..end].parse() {
+            Ok(n) => {
+                self.advance(end);
+                Ok(n)
+            },
+            Err(e) if matches!(e.kind(), IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) =>
+                Err(Fail::new(self.puzzle, self.rest, Expected::InRange, &self.rest[..end])),
+            // Like a `-` in front of an unsigned number.
+            Err(_) => Err(self.fail(Expected::Number)),
+        }
+    }
+
+    // Check that there's nothing left but whitespace.
+    pub fn end(&self) -> Result<(), Fail<'a>> {
+        if self.rest.trim_end().is_empty() {
+            Ok(())
+        } else {
+            Err(Fail::new(self.puzzle, self.rest, Expected::End, rest_of_line(self.rest)))
+        }
+    }
+
+    // Read all of this cursor (usually one made by `take_while`) with `p`.
+    // If it can't be, the whole thing wasn't what was `expected`.
+    pub fn all<T>(mut self, expected: Expected, p: impl FnOnce(&mut Cursor<'a>) -> Result<T, Fail<'a>>)
+        -> Result<T, Fail<'a>>
+    {
+        let whole = self;
+        match p(&mut self).and_then(|t| self.end().map(|_| t)) {
+            Ok(t) => Ok(t),
+            Err(_) => Err(Fail::new(whole.puzzle, whole.rest, expected, whole.rest)),
+        }
+    }
+
+    // Read something with `p`, and check it's `ok`. If it isn't, the whole
+    // of what was read wasn't what was `expected`.
+    pub fn check<T>(&mut self, expected: Expected, p: impl FnOnce(&mut Cursor<'a>) -> Result<T, Fail<'a>>,
+                    ok: impl FnOnce(&T) -> bool) -> Result<T, Fail<'a>>
+    {
+        let start = self.rest;
+        let t = p(self)?;
+        if ok(&t) {
+            Ok(t)
+        } else {
+            let read = &start[..start.len() - self.rest.len()];
+            Err(Fail::new(self.puzzle, start, expected, read))
+        }
+    }
+
+    // Read one or more `item`s, for as long as `sep` finds a separator
+    // between them.
+    pub fn sep_by<T, E>(&mut self, mut sep: impl FnMut(&mut Cursor<'a>) -> bool,
+                        mut item: impl FnMut(&mut Cursor<'a>) -> Result<T, E>) -> Result<Vec<T>, E>
+    {
+        let mut items = vec![item(self)?];
+        while sep(self) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+}
+
+// A cursor for every line of the input with something on it, without any
+// trailing whitespace.
+pub fn lines(puzzle: &str) -> impl Iterator<Item = Cursor<'_>> {
+    puzzle.lines().map(str::trim_end).filter(|line| !line.is_empty()).map(move |rest| Cursor{ puzzle, rest })
+}
+
+// Read each line of the input as a record, which has to take up the whole
+// line.
+pub fn records<'a, T, E: From<Fail<'a>>>(puzzle: &'a str, mut record: impl FnMut(&mut Cursor<'a>) -> Result<T, E>)
+    -> Result<Vec<T>, E>
+{
+    lines(puzzle).map(|mut line| {
+        let t = record(&mut line)?;
+        line.end()?;
+        Ok(t)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        let mut c = Cursor::new("+7 -3 12x4");
+        assert_eq!(c.int::<i32>(), Ok(7));
+        c.skip_while(char::is_whitespace);
+        assert_eq!(c.int::<i64>(), Ok(-3));
+        c.skip_while(char::is_whitespace);
+        assert_eq!(c.int::<u8>(), Ok(12));
+        assert_eq!(c.rest(), "x4");
+
+        let fail = |puzzle| Cursor::new(puzzle).int::<u8>().unwrap_err();
+        assert_eq!(fail("lots of").found, "lots");
+        assert_eq!(fail("-3").expected, Expected::Number);
+        let big = fail("300,");
+        assert_eq!((big.expected, big.found, big.pos), (Expected::InRange, "300", Pos::new(1, 1)));
+    }
+
+    #[test]
+    fn literals() {
+        let puzzle = "Step C must\nStep C can begin";
+        let mut lines = lines(puzzle);
+        let mut line = lines.next().unwrap();
+        assert_eq!(line.literal("Step "), Ok(()));
+        assert_eq!(line.char_if("a step", |c| c.is_ascii_uppercase()), Ok('C'));
+        assert!(!line.eat(" can"));
+
+        let mut line = lines.next().unwrap();
+        line.literal("Step C").unwrap();
+        let fail = line.literal(" must").unwrap_err();
+        assert_eq!(fail, Fail{ pos: Pos::new(2, 7), expected: Expected::Literal(" must"),
+                               found: " can begin", line: "Step C can begin" });
+        assert_eq!(fail.to_string(), "line 2, column 7: expected ` must`, found ` can begin`");
+        assert_eq!(line.char_if("a step", |c| c.is_ascii_uppercase()).unwrap_err().found, " ");
+    }
+
+    #[test]
+    fn combinators() {
+        let mut c = Cursor::new("1, 2,3 4.5");
+        let is_sep = |c: &mut Cursor| !c.take_while(|ch| ch == ',' || ch == ' ').is_empty();
+        let fail = c.sep_by(is_sep, |c| c.take_while(|ch| ch != ',' && ch != ' ').all(Expected::Number, Cursor::int::<u8>))
+            .unwrap_err();
+        assert_eq!((fail.pos, fail.found), (Pos::new(1, 8), "4.5"));
+
+        let mut c = Cursor::new("0 players");
+        let fail = c.check(Expected::Thing("a player"), Cursor::int::<u8>, |&n| n > 0).unwrap_err();
+        assert_eq!((fail.expected, fail.found), (Expected::Thing("a player"), "0"));
+        assert_eq!(c.end().unwrap_err().found, " players");
+    }
+
+    #[test]
+    fn line_records() {
+        let pairs = records("1x2  \n\n3x4\n", |line| -> Result<_, Fail> {
+            let w: u8 = line.int()?;
+            line.literal("x")?;
+            Ok((w, line.int::<u8>()?))
+        });
+        assert_eq!(pairs, Ok(vec![(1, 2), (3, 4)]));
+
+        let fail = records("1x2\n3x4y", |line| -> Result<_, Fail> {
+            line.int::<u8>()?;
+            line.literal("x")?;
+            line.int::<u8>()
+        }).unwrap_err();
+        assert_eq!((fail.pos, fail.expected, fail.found, fail.line), (Pos::new(2, 4), Expected::End, "y", "3x4y"));
+    }
+}
@@ -0,0 +1,209 @@
+// A point (or a vector between points) on a plane, with `y` growing downwards
+// as it does on every map in the puzzles: north is up the page, so towards
+// smaller `y`.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+// What a point's coordinates can be: any of the integer types.
+pub trait Coord: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ONE: Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! coord {
+    ($($t:ty)*) => {$(
+        impl Coord for $t {
+            const ONE: $t = 1;
+            fn checked_add(self, other: $t) -> Option<$t> { <$t>::checked_add(self, other) }
+            fn checked_sub(self, other: $t) -> Option<$t> { <$t>::checked_sub(self, other) }
+            fn to_f64(self) -> f64 { self as f64 }
+        }
+    )*};
+}
+
+coord!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point{ x, y }
+    }
+}
+
+// How far apart two coordinates are, without going below zero on the way for
+// unsigned ones.
+fn apart<T: Coord>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T: Coord> Point<T> {
+    // The distance along the grid lines: moving only up, down, left or right.
+    pub fn manhattan(self, other: Point<T>) -> T {
+        apart(self.x, other.x) + apart(self.y, other.y)
+    }
+
+    // The distance moving diagonally as well, like a king in chess.
+    pub fn chebyshev(self, other: Point<T>) -> T {
+        apart(self.x, other.x).max(apart(self.y, other.y))
+    }
+
+    // The distance as the crow flies.
+    pub fn euclidean(self, other: Point<T>) -> f64 {
+        let (dx, dy) = (apart(self.x, other.x).to_f64(), apart(self.y, other.y).to_f64());
+        dx.hypot(dy)
+    }
+
+    // `self + other`, or None if that can't be represented, like going left
+    // of zero with unsigned coordinates.
+    pub fn checked_add(self, other: Point<T>) -> Option<Point<T>> {
+        Some(Point{ x: self.x.checked_add(other.x)?, y: self.y.checked_add(other.y)? })
+    }
+
+    pub fn checked_sub(self, other: Point<T>) -> Option<Point<T>> {
+        Some(Point{ x: self.x.checked_sub(other.x)?, y: self.y.checked_sub(other.y)? })
+    }
+
+    // The next point over in a direction, if there is one.
+    pub fn step(self, dir: Dir) -> Option<Point<T>> {
+        let Point{ x, y } = self;
+        Some(match dir {
+            Dir::North => Point{ x, y: y.checked_sub(T::ONE)? },
+            Dir::East  => Point{ x: x.checked_add(T::ONE)?, y },
+            Dir::South => Point{ x, y: y.checked_add(T::ONE)? },
+            Dir::West  => Point{ x: x.checked_sub(T::ONE)?, y },
+        })
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point{ x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point{ x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Point<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Point<T>) {
+        *self = *self - other;
+    }
+}
+
+// Scaling, as in moving `n` steps at a velocity.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, n: T) -> Point<T> {
+        Point{ x: self.x * n, y: self.y * n }
+    }
+}
+
+// Written the way the puzzles ask for coordinates: `x,y`.
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+// The four ways to face on a map, clockwise from north.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Dir { North, East, South, West }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Turn { Left, Straight, Right }
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::North, Dir::East, Dir::South, Dir::West];
+
+    // Counter-clockwise.
+    pub fn left(self) -> Dir {
+        Dir::ALL[(self as usize + 3) % 4]
+    }
+
+    // Clockwise.
+    pub fn right(self) -> Dir {
+        Dir::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn reverse(self) -> Dir {
+        Dir::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn turn(self, turn: Turn) -> Dir {
+        match turn {
+            Turn::Left => self.left(),
+            Turn::Straight => self,
+            Turn::Right => self.right(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let (p, v) = (Point::new(3, -2), Point::new(-1, 4));
+        assert_eq!(p + v, Point::new(2, 2));
+        assert_eq!(p - v, Point::new(4, -6));
+        assert_eq!(p + v * 3, Point::new(0, 10));
+        let mut q = p;
+        q += v;
+        q -= p;
+        assert_eq!(q, v);
+        assert_eq!(p.to_string(), "3,-2");
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point::new(1u16, 6), Point::new(4u16, 2));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(b.manhattan(a), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+        assert_eq!(Point::new(-2i8, 0).manhattan(Point::new(2, -3)), 7);
+    }
+
+    #[test]
+    fn stepping() {
+        let corner = Point::new(0usize, 0);
+        assert_eq!(corner.step(Dir::North), None);
+        assert_eq!(corner.step(Dir::West), None);
+        assert_eq!(corner.step(Dir::South), Some(Point::new(0, 1)));
+        assert_eq!(Point::new(u8::MAX, 1).step(Dir::East), None);
+        assert_eq!(corner.checked_sub(Point::new(0, 1)), None);
+        assert_eq!(Point::new(2usize, 3).checked_add(Point::new(1, 1)), Some(Point::new(3, 4)));
+    }
+
+    #[test]
+    fn turning() {
+        assert_eq!(Dir::North.left(), Dir::West);
+        assert_eq!(Dir::West.right(), Dir::North);
+        assert_eq!(Dir::East.reverse(), Dir::West);
+        assert_eq!(Dir::South.turn(Turn::Straight), Dir::South);
+        assert_eq!(Dir::ALL.iter().map(|d| d.turn(Turn::Right).turn(Turn::Left)).collect::<Vec<_>>(), Dir::ALL);
+    }
+}
@@ -0,0 +1,296 @@
+// Calibrating the device as its changes come in, rather than once they've all
+// been read: from a list too big to read in all at once, or one piped in live
+// from whatever's producing it. Each change is read, applied and forgotten, so
+// all that's kept is the frequencies reached, to notice one coming up twice -
+// and once one has, not even those.
+//
+// There's no going round the list again here (a live list has no end to go
+// round from), so a repeat is only noticed if it happens the first time
+// through. `first_repeat` answers for the whole, looping, list.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use aoc_common::Answer;
+use aoc_common::diagnostic::Pos;
+
+use crate::{overflow, ParseError};
+
+// The frequency after a change.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Reading {
+    // Which change it was, counting from 0.
+    pub change: u64,
+    pub frequency: i64,
+    // Whether this is the first time any frequency has come up twice.
+    pub repeat: bool,
+}
+
+#[derive(Debug)]
+pub struct Calibrator {
+    frequency: i64,
+    changes: u64,
+    // Every frequency so far, until one comes up twice.
+    seen: Option<HashSet<i64>>,
+    repeat: Option<Reading>,
+}
+
+impl Calibrator {
+    pub fn new() -> Calibrator {
+        Calibrator{ frequency: 0, changes: 0, seen: Some([0].iter().copied().collect()), repeat: None }
+    }
+
+    pub fn frequency(&self) -> i64 {
+        self.frequency
+    }
+
+    // How many changes have been applied.
+    pub fn changes(&self) -> u64 {
+        self.changes
+    }
+
+    // The first frequency reached twice, if one has been yet.
+    pub fn repeat(&self) -> Option<Reading> {
+        self.repeat
+    }
+
+    pub fn apply(&mut self, change: i64) -> Answer<Reading> {
+        self.frequency = self.frequency.checked_add(change).ok_or_else(overflow)?;
+        let mut reading = Reading{ change: self.changes, frequency: self.frequency, repeat: false };
+        self.changes += 1;
+        if let Some(seen) = &mut self.seen {
+            if !seen.insert(self.frequency) {
+                reading.repeat = true;
+                self.repeat = Some(reading);
+                self.seen = None;
+            }
+        }
+        Ok(reading)
+    }
+}
+
+impl Default for Calibrator {
+    fn default() -> Calibrator {
+        Calibrator::new()
+    }
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Read(io::Error),
+    // Where the change is counts from the start of the whole stream.
+    BadChange(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Read(err) => write!(f, "couldn't read the changes: {}", err),
+            StreamError::BadChange(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamError::Read(err) => Some(err),
+            StreamError::BadChange(err) => Some(err),
+        }
+    }
+}
+
+// Every change in a list being read, as soon as it is - in the same format as
+// the puzzle input, but without waiting for the rest of the line it's on, which
+// for changes all on one line is the whole list.
+pub struct Changes<R> {
+    reader: R,
+    // Where the next byte read is.
+    line: usize,
+    column: usize,
+}
+
+pub fn changes<R: BufRead>(reader: R) -> Changes<R> {
+    Changes{ reader, line: 1, column: 1 }
+}
+
+// What's between changes, besides the end of a line.
+fn separator(b: u8) -> bool {
+    matches!(b, b',' | b' ' | b'\t' | b'\r')
+}
+
+impl<R: BufRead> Changes<R> {
+    // The text of the next change and where it starts, read up to and past
+    // the separator after it - or nothing, at the end of the list.
+    fn next_change(&mut self) -> io::Result<Option<(Pos, Vec<u8>)>> {
+        let mut change = Vec::new();
+        let mut start = Pos::new(self.line, self.column);
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if buf.is_empty() {
+                return Ok(Some((start, change)).filter(|(_, change)| !change.is_empty()));
+            }
+
+            let mut used = 0;
+            let mut ended = false;
+            for &b in buf {
+                used += 1;
+                if b == b'\n' || separator(b) {
+                    if b == b'\n' {
+                        self.line += 1;
+                        self.column = 1;
+                    } else {
+                        self.column += 1;
+                    }
+                    if !change.is_empty() {
+                        ended = true;
+                        break;
+                    }
+                } else {
+                    if change.is_empty() {
+                        start = Pos::new(self.line, self.column);
+                    }
+                    change.push(b);
+                    // Columns count characters, which start with any byte
+                    // but a UTF-8 continuation byte.
+                    if b & 0xc0 != 0x80 {
+                        self.column += 1;
+                    }
+                }
+            }
+            self.reader.consume(used);
+            if ended {
+                return Ok(Some((start, change)));
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Changes<R> {
+    type Item = Result<i64, StreamError>;
+
+    fn next(&mut self) -> Option<Result<i64, StreamError>> {
+        loop {
+            let (start, change) = match self.next_change() {
+                Ok(change) => change?,
+                Err(err) => return Some(Err(StreamError::Read(err))),
+            };
+            let change = match String::from_utf8(change) {
+                Ok(change) => change,
+                Err(err) => return Some(Err(StreamError::Read(io::Error::new(io::ErrorKind::InvalidData, err)))),
+            };
+            match crate::parse(&change) {
+                // Nothing but other whitespace isn't a change, but isn't wrong either.
+                Ok(changes) => if let Some(&change) = changes.first() {
+                    return Some(Ok(change));
+                },
+                Err(ParseError::BadChange { pos, found }) => {
+                    let pos = Pos::new(start.line, start.column + pos.column - 1);
+                    return Some(Err(StreamError::BadChange(ParseError::BadChange { pos, found })));
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufReader, Read};
+
+    use aoc_common::diagnostic::Diagnostic;
+
+    use crate::{first_repeat, parse, Day01};
+
+    fn stream(text: &str) -> Vec<i64> {
+        changes(text.as_bytes()).collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn streaming() {
+        assert_eq!(stream("+1\n-2, +3\n\n+1"), [1, -2, 3, 1]);
+        assert_eq!(stream(""), Vec::<i64>::new());
+
+        let mut bad = changes("+1\n+2, x\n+3".as_bytes());
+        assert_eq!(bad.next().map(Result::ok), Some(Some(1)));
+        assert_eq!(bad.next().map(Result::ok), Some(Some(2)));
+        match bad.next() {
+            Some(Err(StreamError::BadChange(err))) => assert_eq!(err.pos(), Pos::new(2, 5)),
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(bad.next().map(Result::ok), Some(Some(3)));
+        match changes("+1, +2,\t+3é".as_bytes()).nth(2) {
+            Some(Err(StreamError::BadChange(err))) => assert_eq!(err.pos(), Pos::new(1, 9)),
+            other => panic!("{:?}", other),
+        }
+
+        let mut unreadable = changes(&[b'+', 0xff, b'\n'][..]);
+        assert!(matches!(unreadable.next(), Some(Err(StreamError::Read(_)))));
+    }
+
+    // Hands out a few bytes at a time, keeping count of how many so far.
+    struct Trickle<'a> {
+        text: &'a [u8],
+        read: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(3).min(self.text.len() - self.read);
+            buf[..n].copy_from_slice(&self.text[self.read..self.read + n]);
+            self.read += n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn one_long_line() {
+        let text = (0..100_000).map(|n| format!("{:+}", n % 7 - 3)).collect::<Vec<_>>().join(", ");
+        let mut stream = changes(BufReader::with_capacity(4, Trickle{ text: text.as_bytes(), read: 0 }));
+        assert_eq!(stream.next().map(Result::ok), Some(Some(-3)));
+        // Only as far as the end of the first change, not the line.
+        assert!(stream.reader.get_ref().read <= 8);
+        let rest = stream.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(rest, &parse(&text).unwrap()[1..]);
+    }
+
+    #[test]
+    fn calibrating() {
+        let mut calibrator = Calibrator::new();
+        let readings = [1, -1, 1, -1].iter().map(|&n| calibrator.apply(n).unwrap()).collect::<Vec<_>>();
+        assert_eq!(readings.iter().map(|r| r.frequency).collect::<Vec<_>>(), [1, 0, 1, 0]);
+        // Only the first repeat is one.
+        assert_eq!(readings.iter().map(|r| r.repeat).collect::<Vec<_>>(), [false, true, false, false]);
+        assert_eq!(calibrator.repeat(), Some(Reading{ change: 1, frequency: 0, repeat: true }));
+        assert_eq!((calibrator.frequency(), calibrator.changes()), (0, 4));
+
+        let mut calibrator = Calibrator::new();
+        calibrator.apply(i64::MAX).unwrap();
+        assert!(calibrator.apply(1).is_err());
+    }
+
+    #[test]
+    fn against_first_repeat() {
+        // Whenever the looping list repeats the first time through, so does
+        // the stream, at the same change.
+        for (seed, list) in aoc_common::generate::inputs::<Day01>(50, 40) {
+            let repeat = first_repeat(&list).unwrap();
+            let mut calibrator = Calibrator::new();
+            for &change in &list {
+                calibrator.apply(change).unwrap();
+            }
+            match calibrator.repeat() {
+                Some(reading) => assert_eq!((repeat.pass, repeat.change as u64, repeat.frequency),
+                                           (0, reading.change, reading.frequency), "seed {}", seed),
+                None => assert!(repeat.pass > 0, "seed {}", seed),
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use serde::Serialize;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::{self, Cursor, Expected, Fail};
+
+pub mod calibrate;
+pub mod report;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // Every change should be a signed number, like `+7` or `-3`.
+    BadChange { pos: Pos, found: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadChange { pos, found } =>
+                write!(f, "{}: expected a frequency change like `+7`, found `{}`", pos, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::BadChange { pos, .. } => *pos,
+        }
+    }
+}
+
+impl From<Fail<'_>> for ParseError {
+    fn from(fail: Fail) -> ParseError {
+        ParseError::BadChange { pos: fail.pos, found: fail.found.to_string() }
+    }
+}
+
+// Changes come one per line, or all on one line separated by commas.
+fn separator(c: char) -> bool {
+    matches!(c, ',' | ' ' | '\t')
+}
+
+pub fn parse(s: &str) -> Result<Vec<i64>, ParseError> {
+    let lines = parse::records(s, |line| {
+        line.skip_while(separator);
+        line.sep_by(|line| !line.take_while(separator).is_empty() && !line.is_empty(),
+                    |line| line.take_while(|c| !separator(c)).all(Expected::Number, Cursor::int))
+    })?;
+    Ok(lines.into_iter().flatten().collect())
+}
+
+pub(crate) fn overflow() -> NoAnswer {
+    NoAnswer::new("the frequency is too far from zero to count")
+}
+
+pub(crate) fn freq(nums: &[i64]) -> Answer<i64> {
+    nums.iter().try_fold(0i64, |sum, n| sum.checked_add(*n)).ok_or_else(overflow)
+}
+
+pub fn part1(frequencies: &[i64]) -> Answer<i64> {
+    freq(frequencies)
+}
+
+fn no_changes() -> NoAnswer {
+    NoAnswer::new("there are no changes, so the frequency never changes to repeat")
+}
+
+fn no_repeat(drift: impl fmt::Display) -> NoAnswer {
+    NoAnswer::new(format!("no frequency comes up twice, drifting by {} each time through", drift))
+}
+
+// The first frequency reached twice, and when.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Repeat {
+    pub frequency: i64,
+    // Which time through the list it's reached again, and by which change in
+    // it (both counting from 0).
+    pub pass: u64,
+    pub change: usize,
+}
+
+// Work out the first repeat, rather than going round and round the list
+// looking for it (which takes as many passes as the answer does).
+//
+// Every time through, the frequencies are the first time's moved along by
+// the drift. So unless one comes up twice the first time through, a frequency
+// only comes up again when one of the first time's catches up with another a
+// whole number of drifts ahead of it - one with the same remainder, divided
+// by the drift. The first to do so is one right behind another: the fewest
+// drifts behind, and of those, the earliest in the list.
+//
+// Unlike `search`, this doesn't mind the frequency going too far from zero on
+// the way to the answer, as long as the answer itself isn't.
+pub fn first_repeat(frequencies: &[i64]) -> Answer<Repeat> {
+    if frequencies.is_empty() {
+        return Err(no_changes());
+    }
+    let n = frequencies.len();
+    // The frequency after each number of changes, from none to all of them.
+    let sums = std::iter::once(0)
+        .chain(frequencies.iter().scan(0i128, |sum, &change| { *sum += change as i128; Some(*sum) }))
+        .collect::<Vec<_>>();
+    let drift = sums[n];
+    // The repeat that `time` changes in, counting every pass.
+    let repeat = |time: u128, frequency: i128| Ok(Repeat {
+        frequency: i64::try_from(frequency).map_err(|_| overflow())?,
+        pass: u64::try_from((time - 1) / n as u128).map_err(|_| overflow())?,
+        change: ((time - 1) % n as u128) as usize,
+    });
+
+    let mut seen = HashSet::new();
+    for (time, &sum) in sums.iter().enumerate() {
+        if !seen.insert(sum) {
+            return repeat(time as u128, sum);
+        }
+    }
+
+    // Without a repeat so far there's drift (or the last change would have
+    // come back to 0), and the first time's frequencies are all different.
+    let step = drift.abs();
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&i| (sums[i].rem_euclid(step), sums[i] * drift.signum()));
+    log::debug!("Drift: {}, frequencies by remainder: {:?}", drift, order);
+    order.windows(2)
+        .filter(|pair| sums[pair[0]].rem_euclid(step) == sums[pair[1]].rem_euclid(step))
+        .map(|pair| {
+            let (behind, ahead) = (pair[0], pair[1]);
+            let drifts = ((sums[ahead] - sums[behind]) / drift) as u128;
+            (drifts * n as u128 + behind as u128, sums[ahead])
+        })
+        .min()
+        .map_or_else(|| Err(no_repeat(drift)), |(time, frequency)| repeat(time, frequency))
+}
+
+pub fn part2(frequencies: &[i64]) -> Answer<i64> {
+    log::trace!("Frequencies: {:?}", frequencies);
+    first_repeat(frequencies).map(|repeat| repeat.frequency)
+}
+
+// The first repeat the straightforward way, for `first_repeat` to answer to:
+// go through the list until a frequency comes up twice, keeping every one.
+pub fn search(frequencies: &[i64]) -> Answer<i64> {
+    if frequencies.is_empty() {
+        return Err(no_changes());
+    }
+    let drift = freq(frequencies)?;
+
+    let mut cur = 0i64;
+    let mut seen = HashSet::<i64>::new();
+    seen.insert(cur); // Not really a better way to initialize a map with default values
+
+    // Every time through the list, the frequencies are the first time's
+    // moved along by the drift. So for one to come up again, two of the first
+    // time's have to be a whole number of drifts apart - and they can't be
+    // more drifts apart than the widest gap between any of them.
+    let (mut pass, mut passes) = (0, 1);
+    while pass < passes {
+        for n in frequencies {
+            cur = cur.checked_add(*n).ok_or_else(overflow)?;
+            if !seen.insert(cur) {
+                return Ok(cur);
+            }
+        }
+        if pass == 0 {
+            let span = *seen.iter().max().unwrap() as i128 - *seen.iter().min().unwrap() as i128;
+            passes = (span as u128 / drift.unsigned_abs() as u128) as u64 + 1;
+        }
+        pass += 1;
+    }
+    Err(no_repeat(drift))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<i64>;
+    type Config = ();
+    type Answer1 = i64;
+    type Answer2 = i64;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Vec<i64>, ParseError> { parse(puzzle) }
+    fn part1(frequencies: &Vec<i64>, _: &()) -> Answer<i64> { part1(frequencies) }
+    fn part2(frequencies: &Vec<i64>, _: &()) -> Answer<i64> { part2(frequencies) }
+}
+
+impl Generate for Day01 {
+    const SIZE: usize = 1000;
+
+    // `size` changes, drifting by less than `size` each time around, so that
+    // some frequency is bound to come up twice: of the first time around's
+    // frequencies, two have to be a whole number of drifts apart, and then
+    // the one behind catches up to the other.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1) as i64;
+        let mut changes = (1..size)
+            .map(|_| rng.between(1, 20) * if rng.chance(0.5) { 1 } else { -1 })
+            .collect::<Vec<_>>();
+        let drift = rng.between(-(size - 1), size - 1);
+        changes.push(drift - changes.iter().sum::<i64>());
+        changes.iter().map(|change| format!("{:+}\n", change)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::generated!(Day01, 50);
+
+    #[test]
+    fn example_1() {
+        let tests = vec!(
+            ("+1, +1, +1",   3),
+            ("+1, +1, -2",   0),
+            ("-1, -2, -3",  -6));
+        for tt in tests {
+            assert_eq!(part1(&parse(tt.0).unwrap()), Ok(tt.1));
+        }
+    }
+
+    #[test]
+    fn example_2() {
+        let tests = vec!(
+            ("+1, -1",  0),
+            ("+3, +3, +4, -2, -4",  10),
+            ("-6, +3, +8, +5, -6",  5),
+            ("+7, +7, -2, -7, -4",  14));
+        for tt in tests {
+            assert_eq!(part2(&parse(tt.0).unwrap()), Ok(tt.1));
+        }
+    }
+
+    #[test]
+    fn no_repeat() {
+        assert_eq!(part2(&parse("+10, -9").unwrap()), Ok(10));
+        assert!(part2(&parse("+1, +1").unwrap()).is_err());
+        assert!(part2(&parse("").unwrap()).is_err());
+        assert!(part1(&parse("+9223372036854775807, +1").unwrap()).is_err());
+        assert!(part2(&parse("+9223372036854775807, -1").unwrap()).is_err());
+        // Far from zero on the way, but not at the answer.
+        let changes = parse("+9223372036854775807, -9223372036854775806").unwrap();
+        assert!(search(&changes).is_err());
+        assert_eq!(first_repeat(&changes), Ok(Repeat{ frequency: 9223372036854775807, pass: 9223372036854775806, change: 1 }));
+    }
+
+    #[test]
+    fn when() {
+        let repeat = |changes| first_repeat(&parse(changes).unwrap()).unwrap();
+        assert_eq!(repeat("+1, -1"), Repeat{ frequency: 0, pass: 0, change: 1 });
+        assert_eq!(repeat("+3, +3, +4, -2, -4"), Repeat{ frequency: 10, pass: 1, change: 1 });
+        assert_eq!(repeat("+1, +1, -1"), Repeat{ frequency: 1, pass: 0, change: 2 });
+        assert_eq!(repeat("+7, +7, -2, -7, -4"), Repeat{ frequency: 14, pass: 2, change: 2 });
+    }
+
+    #[test]
+    fn bad_change() {
+        assert_eq!(parse("+1\n+2\n+x3"),
+                   Err(ParseError::BadChange { pos: Pos::new(3, 1), found: String::from("+x3") }));
+        assert_eq!(parse("+1, -2, 3.5").unwrap_err().pos(), Pos::new(1, 9));
+    }
+
+    // The slow way to find a repeat: keep every frequency in a list, and look
+    // through the whole list each time.
+    fn slow_repeat(changes: &[i64]) -> Repeat {
+        let (mut cur, mut seen) = (0, vec![0]);
+        for (time, change) in changes.iter().cycle().enumerate() {
+            cur += change;
+            if seen.contains(&cur) {
+                return Repeat{ frequency: cur, pass: (time / changes.len()) as u64, change: time % changes.len() };
+            }
+            seen.push(cur);
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn against_reference() {
+        for (seed, changes) in aoc_common::generate::inputs::<Day01>(50, 40) {
+            let mut sum = 0;
+            for change in &changes {
+                sum += change;
+            }
+            assert_eq!(part1(&changes), Ok(sum), "seed {}", seed);
+            assert_eq!(first_repeat(&changes), Ok(slow_repeat(&changes)), "seed {}", seed);
+            assert_eq!(part2(&changes), search(&changes), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn against_search() {
+        // Short lists of small changes, plenty of which never repeat.
+        let mut rng = Rng::new(1);
+        let mut never = 0;
+        for _ in 0..5000 {
+            let changes = (0..rng.between(1, 6)).map(|_| rng.between(-6, 6)).collect::<Vec<_>>();
+            match search(&changes) {
+                Ok(frequency) => {
+                    let repeat = slow_repeat(&changes);
+                    assert_eq!(frequency, repeat.frequency, "{:?}", changes);
+                    assert_eq!(first_repeat(&changes), Ok(repeat), "{:?}", changes);
+                },
+                Err(why) => {
+                    assert_eq!(first_repeat(&changes), Err(why), "{:?}", changes);
+                    never += 1;
+                },
+            }
+        }
+        assert!(never > 100);
+    }
+}
@@ -1,61 +1,96 @@
-use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process;
 
-fn parse(s: &str) -> Vec<i64> {
-    s.split(|c| c == ',' || c == ' ' || c == '\n')
-        .filter(|s| !s.is_empty())
-        .map(|s| s.parse().unwrap())
-        .collect()
-}
+use aoc_2018_01::Day01;
+use aoc_2018_01::calibrate::{self, Calibrator};
+use aoc_common::diagnostic;
+use aoc_common::flag;
+use aoc_common::input::{self, Source};
+use aoc_common::logger;
 
-fn freq(nums: Vec<i64>) -> i64 { nums.iter().sum() }
+// Like every other day, this solves the puzzle - unless it's asked to
+// `calibrate`, which reads the changes as they come instead, printing the
+// frequency after each one, and calling out the first to come up twice; or to
+// `report` on how the frequency drifts, as a table or JSON:
+//
+//     tail -f changes.log | cargo run -p aoc-2018-01 -- calibrate [path/to/changes | -]
+//     cargo run -p aoc-2018-01 -- report [path/to/input | -] [--passes <n>] [--format <table|json>]
+fn main() {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    let command = match args.first().map(String::as_str) {
+        Some("calibrate") => calibrate,
+        Some("report") => report,
+        _ => return aoc_common::main::<Day01>(&()),
+    };
+    args.remove(0);
 
-fn part1(s: &str) -> i64 {
-    freq(parse(s))
+    logger::init();
+    if let Err(e) = logger::configure(&mut args) {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    }
+    if let Err(e) = command(args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
 }
 
-fn part2(s: &str) -> i64 {
-    let frequencies = parse(s);
-    //println!("Frequencies: {:?}", frequencies);
-
-    let mut cur = 0;
-    let mut seen = HashSet::<i64>::new();
-    seen.insert(cur); // Not really a better way to initialize a map with default values
-    frequencies.iter().cycle().find_map(|n| {
-        cur += n;
-        seen.replace(cur)
-    }).unwrap()
+// Where the changes come from: the first argument that isn't a `--flag`.
+fn source(args: Vec<String>) -> Source {
+    let arg = args.into_iter().find(|arg| arg == "-" || !arg.starts_with("--"));
+    Source::from_arg(arg.as_deref())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn example_1() {
-        let tests = vec!(
-            ("+1, +1, +1",   3),
-            ("+1, +1, -2",   0),
-            ("-1, -2, -3",  -6));
-        for tt in tests {
-            assert_eq!(part1(tt.0), tt.1);
-        }
+fn calibrate(args: Vec<String>) -> Result<(), String> {
+    let source = source(args);
+    let name = source.name(1);
+    let open = |path| File::open(path).map_err(|e| format!("couldn't read the changes from {}: {}", name, e));
+    let reader: Box<dyn BufRead> = match &source {
+        Source::Stdin => Box::new(io::stdin().lock()),
+        Source::Default => Box::new(BufReader::new(open(input::default_path(1))?)),
+        Source::File(path) => Box::new(BufReader::new(open(path.clone())?)),
+    };
+
+    let mut calibrator = Calibrator::new();
+    let mut out = io::stdout().lock();
+    for change in calibrate::changes(reader) {
+        let change = change.map_err(|e| format!("{}: {}", name, e))?;
+        let reading = calibrator.apply(change)
+            .map_err(|why| format!("no frequency after change {} ({})", calibrator.changes(), why))?;
+        let written = if reading.repeat {
+            writeln!(out, "{}\trepeat: {} has come up twice", reading.frequency, reading.frequency)
+        } else {
+            writeln!(out, "{}", reading.frequency)
+        };
+        written.map_err(|e| format!("couldn't write out the frequency: {}", e))?;
     }
 
-    #[test]
-    fn example_2() {
-        let tests = vec!(
-            ("+1, -1",  0),
-            ("+3, +3, +4, -2, -4",  10),
-            ("-6, +3, +8, +5, -6",  5),
-            ("+7, +7, -2, -7, -4",  14));
-        for tt in tests {
-            assert_eq!(part2(tt.0), tt.1);
-        }
+    if calibrator.repeat().is_none() {
+        log::info!("No frequency came up twice in {} changes", calibrator.changes());
     }
+    Ok(())
 }
 
-fn main() {
-    let input = include_str!("../input");
-    println!("part1: {}", part1(&input));
-    println!("part2: {}", part2(&input));
+fn report(mut args: Vec<String>) -> Result<(), String> {
+    let passes = flag(&mut args, "--passes")?
+        .map(|n| n.parse::<u64>().map_err(|_| format!("expected a number of passes, not {:?}", n)))
+        .transpose()?;
+    let json = match flag(&mut args, "--format")?.as_deref() {
+        None | Some("table") => false,
+        Some("json") => true,
+        Some(other) => return Err(format!("expected a format of table or json, not {:?}", other)),
+    };
+    let source = source(args);
+    let puzzle = input::read(1, &source).map_err(|e| e.to_string())?;
+    let changes = aoc_2018_01::parse(&puzzle)
+        .map_err(|e| diagnostic::render(&e, &puzzle, &source.name(1)).trim_end().to_string())?;
+
+    let report = aoc_2018_01::report::report(&changes, passes).map_err(|why| format!("no report ({})", why))?;
+    let text = if json {
+        serde_json::to_string_pretty(&report).map_err(|e| e.to_string())? + "\n"
+    } else {
+        report.to_string()
+    };
+    io::stdout().lock().write_all(text.as_bytes()).map_err(|e| format!("couldn't write out the report: {}", e))
 }
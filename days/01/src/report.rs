@@ -0,0 +1,265 @@
+// A closer look at a list of changes than the two answers give: when the
+// frequency first repeats and which change does it, how far the frequency
+// wanders, and every frequency that comes up more than once in the first so
+// many passes through the list.
+//
+// The first repeat is worked out rather than walked to, so it's there however
+// far off it is. How far the frequency wanders, and what repeats along the
+// way, is walked - so that only covers so many changes, and of the frequencies
+// walked through, only the ones that can come up again are kept.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use aoc_common::{Answer, NoAnswer};
+
+use crate::{first_repeat, freq, overflow, Repeat};
+
+// The passes are gone through one change at a time, so only so many changes
+// of them are.
+pub const MAX_READINGS: u64 = 10_000_000;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub changes: usize,
+    // How far the frequency moves each time through the list.
+    pub drift: i64,
+    pub first_repeat: Option<Repeat>,
+    // Why there isn't one, when there isn't.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_repeat: Option<String>,
+    // How many times through the list the rest of the report covers...
+    pub passes: u64,
+    // ...unless that's too many changes to go through, and it's cut short
+    // after this many.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<u64>,
+    pub lowest: i64,
+    pub highest: i64,
+    // In the order they come up a second time.
+    pub repeated: Vec<Repeated>,
+}
+
+// A frequency reached more than once.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Repeated {
+    pub frequency: i64,
+    pub times: u64,
+    // How many changes in it was first reached, and then reached again -
+    // counting across passes, with the frequency the device starts at first
+    // reached 0 changes in.
+    pub first: u64,
+    pub again: u64,
+}
+
+// Report on a list of changes, over `passes` times through it: by default,
+// as many as it takes for the first repeat (or just the one, without one).
+pub fn report(frequencies: &[i64], passes: Option<u64>) -> Answer<Report> {
+    report_within(frequencies, passes, MAX_READINGS)
+}
+
+// Which frequencies a walk through `passes` passes (or part of the last) can
+// come to more than once, by where in the list they're come to and in which
+// pass.
+//
+// Every pass, the frequencies are the first pass's moved along by the drift,
+// as `first_repeat` has it: the one `i` changes into pass `p` is some number
+// of drifts on from its remainder (divided by the drift), `p` more than the
+// first pass's was. So it's come to again only if another of the first pass's
+// with the same remainder is few enough drifts behind to catch up with it in
+// time.
+fn repeatable(frequencies: &[i64], passes: u64) -> Box<dyn Fn(usize, u64) -> bool> {
+    let mut sums = std::iter::once(0)
+        .chain(frequencies.iter().scan(0i128, |sum, &change| { *sum += change as i128; Some(*sum) }))
+        .collect::<Vec<_>>();
+    let drift = sums.pop().unwrap_or(0);
+    // Without drift, the first pass's frequencies come round every pass.
+    if drift == 0 {
+        return Box::new(|_, _| true);
+    }
+
+    let step = drift.abs();
+    let drifts = sums.iter().map(|&sum| (sum.rem_euclid(step), (sum - sum.rem_euclid(step)) / drift)).collect::<Vec<_>>();
+    let mut by_remainder = HashMap::<i128, Vec<i128>>::new();
+    for &(remainder, behind) in &drifts {
+        by_remainder.entry(remainder).or_default().push(behind);
+    }
+    for drifts in by_remainder.values_mut() {
+        drifts.sort_unstable();
+    }
+    Box::new(move |i, pass| {
+        let (remainder, behind) = drifts[i];
+        let (at, passes) = (behind + pass as i128, passes as i128);
+        let others = &by_remainder[&remainder];
+        others.partition_point(|&d| d <= at) - others.partition_point(|&d| d < at - passes) > 1
+    })
+}
+
+// Going through at most `max_readings` changes.
+fn report_within(frequencies: &[i64], passes: Option<u64>, max_readings: u64) -> Answer<Report> {
+    if frequencies.is_empty() {
+        return Err(NoAnswer::new("there are no changes to report on"));
+    }
+    let drift = freq(frequencies)?;
+    let repeat = first_repeat(frequencies);
+    let passes = passes.unwrap_or_else(|| repeat.as_ref().map_or(1, |repeat| repeat.pass.saturating_add(1)));
+    let all = passes.saturating_mul(frequencies.len() as u64);
+    let readings = all.min(max_readings);
+    let truncated = Some(readings).filter(|&readings| readings < all);
+
+    // How many times each frequency that can repeat is reached, when first,
+    // and when second.
+    let changes = frequencies.len() as u64;
+    let repeatable = repeatable(frequencies, readings / changes);
+    let mut reached = HashMap::<i64, (u64, u64, u64)>::new();
+    reached.insert(0, (1, 0, 0));
+    let (mut cur, mut lowest, mut highest) = (0i64, 0, 0);
+    for (time, change) in (1..).zip(frequencies.iter().cycle().take(readings as usize)) {
+        cur = cur.checked_add(*change).ok_or_else(overflow)?;
+        lowest = lowest.min(cur);
+        highest = highest.max(cur);
+        if !repeatable((time % changes) as usize, time / changes) {
+            continue;
+        }
+        let entry = reached.entry(cur).or_insert((0, time, 0));
+        entry.0 += 1;
+        if entry.0 == 2 {
+            entry.2 = time;
+        }
+    }
+    let mut repeated = reached.into_iter()
+        .filter(|&(_, (times, _, _))| times > 1)
+        .map(|(frequency, (times, first, again))| Repeated{ frequency, times, first, again })
+        .collect::<Vec<_>>();
+    repeated.sort_unstable_by_key(|repeated| repeated.again);
+
+    let (first_repeat, no_repeat) = match repeat {
+        Ok(repeat) => (Some(repeat), None),
+        Err(why) => (None, Some(why.to_string())),
+    };
+    Ok(Report{ changes: frequencies.len(), drift, first_repeat, no_repeat, passes, truncated, lowest, highest, repeated })
+}
+
+// As a table, for people.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "changes       {}", self.changes)?;
+        writeln!(f, "drift         {:+} each pass", self.drift)?;
+        match (&self.first_repeat, &self.no_repeat) {
+            (Some(repeat), _) => writeln!(f, "first repeat  {}, from change {} of pass {} (counting from 0)",
+                                          repeat.frequency, repeat.change, repeat.pass)?,
+            (None, why) => writeln!(f, "first repeat  none: {}", why.as_deref().unwrap_or("?"))?,
+        }
+        writeln!(f, "passes        {}", self.passes)?;
+        let within = match self.truncated {
+            Some(readings) => {
+                writeln!(f, "truncated     after {} changes, too many to go through", readings)?;
+                format!("the first {} changes", readings)
+            },
+            None => format!("{} passes", self.passes),
+        };
+        writeln!(f, "lowest        {}", self.lowest)?;
+        writeln!(f, "highest       {}", self.highest)?;
+
+        writeln!(f, "\n{} frequencies come up more than once, in {}", self.repeated.len(), within)?;
+        if !self.repeated.is_empty() {
+            writeln!(f, "{:>20}  {:>8}  {:>12}  {:>12}", "frequency", "times", "first", "again")?;
+        }
+        for repeated in &self.repeated {
+            writeln!(f, "{:>20}  {:>8}  {:>12}  {:>12}", repeated.frequency, repeated.times, repeated.first, repeated.again)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::generate::Rng;
+
+    use crate::parse;
+
+    #[test]
+    fn reports() {
+        // 0 3 6 10 8 4 | 7 10 14 12 8 | ...
+        let changes = parse("+3, +3, +4, -2, -4").unwrap();
+        let report = report(&changes, None).unwrap();
+        assert_eq!(report, Report {
+            changes: 5, drift: 4, first_repeat: Some(Repeat{ frequency: 10, pass: 1, change: 1 }), no_repeat: None,
+            passes: 2, truncated: None, lowest: 0, highest: 14,
+            repeated: vec![Repeated{ frequency: 10, times: 2, first: 3, again: 7 },
+                           Repeated{ frequency: 8, times: 2, first: 4, again: 10 }],
+        });
+        assert!(report.to_string().contains("first repeat  10, from change 1 of pass 1"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["first_repeat"]["change"], 1);
+        assert_eq!(json["repeated"][1]["again"], 10);
+        assert!(json.get("no_repeat").is_none() && json.get("truncated").is_none());
+
+        // Round and round, the same few frequencies come up over and over.
+        let report = super::report(&parse("+1, -1").unwrap(), Some(3)).unwrap();
+        assert_eq!(report.repeated, [Repeated{ frequency: 0, times: 4, first: 0, again: 2 },
+                                     Repeated{ frequency: 1, times: 3, first: 1, again: 3 }]);
+    }
+
+    #[test]
+    fn no_repeats() {
+        let report = report(&parse("+1, +1").unwrap(), None).unwrap();
+        assert_eq!((report.first_repeat, report.passes, report.highest), (None, 1, 2));
+        assert!(report.no_repeat.unwrap().contains("drifting by 2"));
+        assert!(report.repeated.is_empty());
+
+        assert!(super::report(&[], None).is_err());
+        assert!(super::report(&[i64::MAX, -1], Some(2)).is_err());
+    }
+
+    #[test]
+    fn against_every_frequency() {
+        // Keeping only the frequencies that can repeat finds the same repeats
+        // as keeping them all.
+        let mut rng = Rng::new(1);
+        for _ in 0..300 {
+            let changes = (0..rng.between(1, 12)).map(|_| rng.between(-6, 6)).collect::<Vec<_>>();
+            let passes = rng.between(1, 8) as u64;
+            let max_readings = rng.between(1, 60) as u64;
+            let readings = (passes * changes.len() as u64).min(max_readings);
+
+            let mut reached = HashMap::<i64, Vec<u64>>::new();
+            reached.entry(0).or_default().push(0);
+            let mut cur = 0;
+            for (time, change) in (1..).zip(changes.iter().cycle().take(readings as usize)) {
+                cur += change;
+                reached.entry(cur).or_default().push(time);
+            }
+            let mut expected = reached.into_iter()
+                .filter(|(_, times)| times.len() > 1)
+                .map(|(frequency, times)| Repeated{ frequency, times: times.len() as u64, first: times[0], again: times[1] })
+                .collect::<Vec<_>>();
+            expected.sort_unstable_by_key(|repeated| repeated.again);
+
+            let report = report_within(&changes, Some(passes), max_readings).unwrap();
+            assert_eq!(report.repeated, expected, "{:?} over {} passes, up to {}", changes, passes, max_readings);
+        }
+    }
+
+    #[test]
+    fn too_many_passes() {
+        // 0 20000000 | 1 20000001 | 2 ... until the frequency has drifted up
+        // to where it first went.
+        let report = report_within(&[20_000_000, -19_999_999], None, 1000).unwrap();
+        assert_eq!(report.first_repeat, Some(Repeat{ frequency: 20_000_000, pass: 19_999_999, change: 1 }));
+        assert_eq!((report.passes, report.truncated), (20_000_000, Some(1000)));
+        assert_eq!((report.lowest, report.highest), (0, 20_000_499));
+        assert!(report.repeated.is_empty());
+        assert!(report.to_string().contains("truncated     after 1000 changes"));
+        assert_eq!(serde_json::to_value(&report).unwrap()["truncated"], 1000);
+
+        let report = report_within(&[1, 1], Some(u64::MAX), 1000).unwrap();
+        assert_eq!((report.truncated, report.highest), (Some(1000), 1000));
+        assert_eq!(report_within(&[1, 1], Some(500), 1000).unwrap().truncated, None);
+    }
+}
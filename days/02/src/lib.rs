@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::{self, Fail};
+
+use crate::matching::{matches, Distance, Match};
+
+pub mod matching;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // Box IDs are made of lowercase letters only.
+    BadLetter { pos: Pos, found: char },
+    // All of the IDs are the same length, or comparing them makes no sense.
+    BadLength { pos: Pos, expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadLetter { pos, found } =>
+                write!(f, "{}: expected a lowercase letter, found `{}`", pos, found),
+            ParseError::BadLength { pos, expected, found } =>
+                write!(f, "{}: expected a box ID of {} letters, found {}", pos, expected, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::BadLetter { pos, .. } | ParseError::BadLength { pos, .. } => *pos,
+        }
+    }
+}
+
+// Anything but a letter after an ID starts is a bad letter.
+impl From<Fail<'_>> for ParseError {
+    fn from(fail: Fail) -> ParseError {
+        ParseError::BadLetter { pos: fail.pos, found: fail.found.chars().next().unwrap_or('\n') }
+    }
+}
+
+pub fn parse(puzzle: &str) -> Result<Vec<String>, ParseError> {
+    let mut len = None;
+    parse::records(puzzle, |line| {
+        line.skip_while(char::is_whitespace);
+        // Only worked out for a bad ID: finding a position means counting
+        // lines from the start, which would take forever for every ID.
+        let start = *line;
+        let id = line.take_while(|c| c.is_ascii_lowercase()).rest();
+        line.end()?;
+        match *len.get_or_insert(id.len()) {
+            expected if expected != id.len() =>
+                Err(ParseError::BadLength { pos: start.pos(), expected, found: id.len() }),
+            _ => Ok(id.to_string()),
+        }
+    })
+}
+
+pub fn part1(ids: &[String]) -> u64 {
+    let mut twos: u64 = 0;
+    let mut threes: u64 = 0;
+    // rewrite as fold?
+    for id in ids {
+        let mut letters = HashMap::<char, u32>::new();
+        for c in id.chars() {
+            let e = letters.entry(c).or_insert(0);
+            *e += 1;
+        }
+
+        let counts: HashSet<u32> = letters.values().cloned().collect();
+        if counts.contains(&2) { twos += 1; }
+        if counts.contains(&3) { threes += 1; }
+    }
+
+    twos * threes
+}
+
+// Hashes of every prefix of an ID, and every suffix: the ID without any one
+// letter is then hashed as the prefix before it and the suffix after it,
+// without going through the rest of the ID again.
+struct Hashes {
+    letters: Vec<char>,
+    prefixes: Vec<u64>,
+    suffixes: Vec<u64>,
+}
+
+impl Hashes {
+    const BASE: u64 = 0x100_0000_01B3;
+
+    fn new(id: &str) -> Hashes {
+        let letters = id.chars().collect::<Vec<_>>();
+        let step = |hash: u64, c: &char| hash.wrapping_mul(Hashes::BASE).wrapping_add(*c as u64 + 1);
+        let mut prefixes = vec![0];
+        prefixes.extend(letters.iter().scan(0, |hash, c| { *hash = step(*hash, c); Some(*hash) }));
+        let mut suffixes = letters.iter().rev().scan(0, |hash, c| { *hash = step(*hash, c); Some(*hash) }).collect::<Vec<_>>();
+        suffixes.reverse();
+        suffixes.push(0);
+        Hashes{ letters, prefixes, suffixes }
+    }
+
+    // The ID, with the letter at `position` masked out.
+    fn masked(&self, position: usize) -> (u64, u64) {
+        (self.prefixes[position], self.suffixes[position + 1])
+    }
+
+    // Hashes can collide, so IDs that look like twins have to be checked.
+    fn twin(&self, other: &Hashes, position: usize) -> bool {
+        self.letters[position] != other.letters[position]
+            && self.letters[..position] == other.letters[..position]
+            && self.letters[position + 1..] == other.letters[position + 1..]
+    }
+}
+
+// Every pair of IDs that differ by exactly one letter, in the order they're
+// in the list: for each position in the IDs, the IDs that are the same but
+// for the letter there are found by hashing them without it.
+pub fn twins(ids: &[String]) -> Vec<Match> {
+    let hashes = ids.iter().map(|id| Hashes::new(id)).collect::<Vec<_>>();
+    // Only IDs of the same length can be twins.
+    let mut by_length = HashMap::<usize, Vec<usize>>::new();
+    for (idx, id) in hashes.iter().enumerate() {
+        by_length.entry(id.letters.len()).or_default().push(idx);
+    }
+
+    let mut twins = Vec::new();
+    let mut masked = Vec::new();
+    for (len, group) in by_length {
+        for position in 0..len {
+            // IDs that are the same without this letter end up together,
+            // sorted by the letter.
+            masked.clear();
+            masked.extend(group.iter().map(|&idx| (hashes[idx].masked(position), hashes[idx].letters[position], idx)));
+            masked.sort_unstable();
+            for same in masked.chunk_by(|a, b| a.0 == b.0).filter(|same| same.len() > 1) {
+                // Identical IDs aren't twins, so only pair up IDs with
+                // different letters here - which are all twins, but for
+                // collisions.
+                for (i, &(_, letter, a)) in same.iter().enumerate() {
+                    let others = same[i + 1..].iter().skip_while(|&&(_, other, _)| other == letter);
+                    for &(_, _, b) in others.filter(|&&(_, _, b)| hashes[a].twin(&hashes[b], position)) {
+                        let mut common = hashes[a].letters.clone();
+                        common.remove(position);
+                        twins.push(Match{ ids: (a.min(b), a.max(b)), distance: 1, common: common.into_iter().collect() });
+                    }
+                }
+            }
+        }
+    }
+    twins.sort_unstable();
+    twins
+}
+
+// "What letters are common between the two correct box IDs?"
+// They're the only two that differ by exactly one letter - or by however
+// much they're asked to.
+pub fn part2(ids: &[String], within: &Distance) -> Answer<String> {
+    let mut matches = matches(ids, *within);
+    match matches.len() {
+        1 => Ok(matches.remove(0).common),
+        0 => Err(NoAnswer::new(format!("no two box IDs differ by {}", within))),
+        n => Err(NoAnswer::new(format!("{} pairs of box IDs differ by {}, so which is right isn't clear \
+                                        (the first being {} and {})", n, within, ids[matches[0].ids.0], ids[matches[0].ids.1]))),
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<String>;
+    type Config = Distance;
+    type Answer1 = u64;
+    type Answer2 = String;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Vec<String>, ParseError> { parse(puzzle) }
+    fn part1(ids: &Vec<String>, _: &Distance) -> Answer<u64> { Ok(part1(ids)) }
+    fn part2(ids: &Vec<String>, within: &Distance) -> Answer<String> { part2(ids, within) }
+}
+
+impl Generate for Day02 {
+    const SIZE: usize = 250;
+
+    // `size` random box IDs, two of which differ by a single letter. Any
+    // other pair being that close is vanishingly unlikely, with 26 letters.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut ids = (0..size.max(2) - 1)
+            .map(|_| (0..26).map(|_| b'a' + rng.below(26) as u8).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut twin = rng.pick(&ids).clone();
+        let idx = rng.below(twin.len());
+        twin[idx] = (twin[idx] - b'a' + 1 + rng.below(25) as u8) % 26 + b'a';
+        ids.insert(rng.below(ids.len() + 1), twin);
+
+        ids.iter().map(|id| id.iter().map(|&b| b as char).collect::<String>() + "\n").collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_1() {
+        let test = ["abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab"]
+            .join("\n");
+        let expect = 4 * 3;
+        assert_eq!(part1(&parse(&test).unwrap()), expect);
+    }
+
+    aoc_common::examples!(Day02);
+    aoc_common::generated!(Day02, 20);
+
+    #[test]
+    fn bad_ids() {
+        assert_eq!(parse("abcde\nfgHij"),
+                   Err(ParseError::BadLetter { pos: Pos::new(2, 3), found: 'H' }));
+        assert_eq!(parse("abcde\nfghij\nklmnop"),
+                   Err(ParseError::BadLength { pos: Pos::new(3, 1), expected: 5, found: 6 }));
+    }
+
+    #[test]
+    fn no_twins() {
+        assert!(part2(&parse("abc\nabd\nxyz").unwrap(), &Distance::default()).is_ok());
+        assert!(part2(&parse("abc\nxyz").unwrap(), &Distance::default()).is_err());
+        assert!(part2(&parse("").unwrap(), &Distance::default()).is_err());
+        assert!(part2(&parse("abc\nabd\nabe").unwrap(), &Distance::default()).is_err());
+    }
+
+    #[test]
+    fn all_twins() {
+        let ids = ["abc", "abd", "xyz", "abc", "xbd", "abcd", "ábc"].iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let twin = |a, b, common: &str| Match{ ids: (a, b), distance: 1, common: common.to_string() };
+        assert_eq!(twins(&ids), [twin(0, 1, "ab"), twin(0, 6, "bc"), twin(1, 3, "ab"), twin(1, 4, "bd"), twin(3, 6, "bc")]);
+        assert_eq!(twins(&[]), []);
+    }
+
+    #[test]
+    fn against_pairs() {
+        // Short IDs from a few letters, so plenty of them are twins (and
+        // plenty of them the same).
+        let mut rng = Rng::new(2);
+        for _ in 0..200 {
+            let ids = (0..rng.between(0, 40))
+                .map(|_| (0..rng.between(1, 4)).map(|_| *rng.pick(&['a', 'b', 'c'])).collect::<String>())
+                .collect::<Vec<_>>();
+            let mut expected = Vec::new();
+            for (i, a) in ids.iter().enumerate() {
+                for (j, b) in ids.iter().enumerate().skip(i + 1) {
+                    let diffs = a.chars().zip(b.chars()).filter(|(x, y)| x != y).count();
+                    if a.len() == b.len() && diffs == 1 {
+                        let common = a.chars().zip(b.chars()).filter(|(x, y)| x == y).map(|(x, _)| x).collect();
+                        expected.push(Match{ ids: (i, j), distance: 1, common });
+                    }
+                }
+            }
+            assert_eq!(twins(&ids), expected, "{:?}", ids);
+        }
+    }
+
+    #[test]
+    fn against_reference() {
+        // Count each letter by looking through the whole ID for it, and
+        // compare every pair of IDs.
+        let has = |id: &str, n: usize| id.chars().any(|c| id.chars().filter(|&d| d == c).count() == n);
+        for (seed, ids) in aoc_common::generate::inputs::<Day02>(50, 30) {
+            let twos = ids.iter().filter(|id| has(id, 2)).count() as u64;
+            let threes = ids.iter().filter(|id| has(id, 3)).count() as u64;
+            assert_eq!(part1(&ids), twos * threes, "seed {}", seed);
+
+            let mut common = Vec::new();
+            for (i, a) in ids.iter().enumerate() {
+                for b in &ids[i + 1..] {
+                    let same = a.chars().zip(b.chars()).filter(|(x, y)| x == y).map(|(x, _)| x).collect::<String>();
+                    if same.len() + 1 == a.len() {
+                        common.push(same);
+                    }
+                }
+            }
+            assert_eq!(common.len(), 1, "seed {}", seed);
+            assert_eq!(part2(&ids, &Distance::default()).as_ref(), Ok(&common[0]), "seed {}", seed);
+        }
+    }
+}
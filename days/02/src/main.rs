@@ -1,83 +1,55 @@
-use std::collections::HashMap;
-use std::collections::HashSet;
-
-fn part1(puzzle: &str) -> u32 {
-    let mut twos: u32 = 0;
-    let mut threes: u32 = 0;
-    // rewrite as fold?
-    for line in puzzle.lines() {
-        let mut letters = HashMap::<char, u8>::new();
-        for c in line.chars() {
-            let e = letters.entry(c).or_insert(0);
-            *e += 1;
-        }
-
-        let counts: HashSet<u8> = letters.values().cloned().collect();
-        if counts.contains(&2) { twos += 1; }
-        if counts.contains(&3) { threes += 1; }
-    }
-
-    let checksum = twos * threes;
-    checksum
-}
-
-fn part2(puzzle: &str) -> String {
-    let mut ids: Vec<&str> = puzzle.split_whitespace().collect();
-
-    let mut next = ids.pop();
-    while next.is_some() {
-        let id = next.unwrap();
-        for other in ids.iter() {
-            // Save offending character position
-            let mut maybe_needle: usize = 0;
-
-            assert!(id.len() == other.len());
-
-            // Track differences in strings, break early if more than 1 is a problem
-            let mut diff: u8 = 0;
-            for (index, (a, b)) in id.chars().zip(other.chars()).enumerate() {
-                if a != b {
-                    maybe_needle = index;
-                    diff += 1;
-                    if diff > 1 {
-                        break
-                    }
-                }
-            }
-
-            if diff == 1 {
-                let pos = maybe_needle;
-                return [&id[0..pos], &id[pos+1..]].concat();
-            }
-        }
-        next = ids.pop();
+use std::io::{self, Write};
+use std::process;
+
+use aoc_2018_02::Day02;
+use aoc_2018_02::matching::{matches, Distance};
+use aoc_common::flag;
+use aoc_common::input::{self, Source};
+use aoc_common::logger;
+
+// Like every other day, this solves the puzzle - unless it's asked to `match`
+// labels instead: any list of them, one per line, of any length. Every pair
+// that's nearly the same is printed, along with what they have in common.
+//
+//     cargo run -p aoc-2018-02 -- match [path/to/labels | -] [--hamming <k> | --levenshtein <k>]
+fn main() {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().map(String::as_str) != Some("match") {
+        return aoc_common::main::<Day02>(&Distance::default());
     }
-    panic!("Unable to find ids with one character difference!")
-}
+    args.remove(0);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn example_1() {
-        let test = vec!("abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab")
-            .join("\n");
-        let expect = 4 * 3;
-        assert_eq!(part1(&test), expect);
+    logger::init();
+    if let Err(e) = logger::configure(&mut args) {
+        eprintln!("error: {}", e);
+        process::exit(2);
     }
-
-    #[test]
-    fn example_2() {
-        let test = vec!("abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz")
-            .join("\n");
-        let expect = "fgij";
-        assert_eq!(part2(&test), expect);
+    if let Err(e) = run(args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
     }
 }
 
-fn main() {
-    let input = include_str!("../input");
-    println!("part1: {}", part1(&input));
-    println!("part2: {}", part2(&input));
+fn run(mut args: Vec<String>) -> Result<(), String> {
+    let k = |flag: Option<String>| flag
+        .map(|k| k.parse::<usize>().map_err(|_| format!("expected a number of letters apart, not {:?}", k)))
+        .transpose();
+    let within = match (k(flag(&mut args, "--hamming")?)?, k(flag(&mut args, "--levenshtein")?)?) {
+        (Some(_), Some(_)) => return Err(String::from("labels can be --hamming or --levenshtein apart, not both")),
+        (_, Some(k)) => Distance::Levenshtein(k),
+        (Some(k), _) => Distance::Hamming(k),
+        (None, None) => Distance::default(),
+    };
+    let arg = args.into_iter().find(|arg| arg == "-" || !arg.starts_with("--"));
+    let source = Source::from_arg(arg.as_deref());
+    let text = input::read(2, &source).map_err(|e| e.to_string())?;
+    let labels = text.lines().map(str::trim).filter(|label| !label.is_empty()).map(String::from).collect::<Vec<_>>();
+
+    let matches = matches(&labels, within);
+    let mut out = io::stdout().lock();
+    for m in matches {
+        writeln!(out, "{}\t{}\t{}\t{}", labels[m.ids.0], labels[m.ids.1], m.distance, m.common)
+            .map_err(|e| format!("couldn't write out the matches: {}", e))?;
+    }
+    Ok(())
 }
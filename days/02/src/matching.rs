@@ -0,0 +1,246 @@
+// Finding box IDs - or any other labels - that are nearly the same, for some
+// idea of nearly: a few letters different in the same places (for labels of
+// the same length), or a few letters added, taken away or changed (for any).
+// The puzzle's own "differ by exactly one character" is the first, with one
+// letter.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::twins;
+
+// How far apart two IDs can be and still match.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Distance {
+    // Letters that are different in the same place. Only IDs of the same
+    // length can be any number of them apart.
+    Hamming(usize),
+    // Letters added, taken away or changed to make one ID the other.
+    Levenshtein(usize),
+}
+
+// What the puzzle asks for.
+impl Default for Distance {
+    fn default() -> Distance {
+        Distance::Hamming(1)
+    }
+}
+
+impl fmt::Display for Distance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Distance::Hamming(1) => write!(f, "exactly one letter"),
+            Distance::Hamming(k) => write!(f, "up to {} letters in place", k),
+            Distance::Levenshtein(1) => write!(f, "exactly one edit"),
+            Distance::Levenshtein(k) => write!(f, "up to {} edits", k),
+        }
+    }
+}
+
+// Two IDs that match: different, but no further apart than they can be.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Match {
+    // Where the two IDs are in the list, the earlier one first.
+    pub ids: (usize, usize),
+    pub distance: usize,
+    // The letters they have in common, in order: the ones in the same place,
+    // or for edits, the ones that are neither added, taken away, nor changed.
+    pub common: String,
+}
+
+// Every pair of IDs within `within` of each other (identical IDs aren't), in
+// the order they're in the list.
+pub fn matches(ids: &[String], within: Distance) -> Vec<Match> {
+    let mut matches = match within {
+        Distance::Hamming(0) | Distance::Levenshtein(0) => Vec::new(),
+        // Masking each letter in turn finds these quickest.
+        Distance::Hamming(1) => twins(ids),
+        Distance::Hamming(k) => hamming(ids, k),
+        Distance::Levenshtein(k) => levenshtein(ids, k),
+    };
+    matches.sort_unstable();
+    matches
+}
+
+fn letters(ids: &[String]) -> Vec<Vec<char>> {
+    ids.iter().map(|id| id.chars().collect()).collect()
+}
+
+// Split the IDs of each length into `k + 1` blocks: two that are at most `k`
+// letters apart have to be the same in at least one of them. So only IDs
+// that share a block are compared, each pair in the first block they share.
+fn hamming(ids: &[String], k: usize) -> Vec<Match> {
+    let ids = letters(ids);
+    let mut by_length = HashMap::<usize, Vec<usize>>::new();
+    for (idx, id) in ids.iter().enumerate() {
+        by_length.entry(id.len()).or_default().push(idx);
+    }
+
+    let mut matches = Vec::new();
+    for (len, group) in by_length {
+        let blocks = (0..=k).map(|nth| nth * len / (k + 1)..(nth + 1) * len / (k + 1)).collect::<Vec<_>>();
+        for (nth, block) in blocks.iter().enumerate() {
+            let mut sharing = HashMap::<&[char], Vec<usize>>::new();
+            for &idx in &group {
+                sharing.entry(&ids[idx][block.clone()]).or_default().push(idx);
+            }
+            for same in sharing.values().filter(|same| same.len() > 1) {
+                for (i, &a) in same.iter().enumerate() {
+                    for &b in &same[i + 1..] {
+                        let (x, y) = (&ids[a], &ids[b]);
+                        if blocks[..nth].iter().any(|earlier| x[earlier.clone()] == y[earlier.clone()]) {
+                            continue;
+                        }
+                        let distance = x.iter().zip(y).filter(|(p, q)| p != q).count();
+                        if (1..=k).contains(&distance) {
+                            let common = x.iter().zip(y).filter(|(p, q)| p == q).map(|(p, _)| p).collect();
+                            matches.push(Match{ ids: (a, b), distance, common });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    matches
+}
+
+// Every pair of IDs whose lengths are close enough, edited the slow way.
+fn levenshtein(ids: &[String], k: usize) -> Vec<Match> {
+    let ids = letters(ids);
+    let mut matches = Vec::new();
+    for (a, x) in ids.iter().enumerate() {
+        for (b, y) in ids.iter().enumerate().skip(a + 1) {
+            if x.len().max(y.len()) - x.len().min(y.len()) > k {
+                continue;
+            }
+            if let Some((distance, common)) = edits(x, y, k).filter(|&(distance, _)| distance > 0) {
+                matches.push(Match{ ids: (a, b), distance, common });
+            }
+        }
+    }
+    matches
+}
+
+// The fewest edits that make `x` into `y`, and the letters those edits leave
+// alone - or nothing, if it takes more than `k`.
+fn edits(x: &[char], y: &[char], k: usize) -> Option<(usize, String)> {
+    // How many edits make the first `i` letters of `x` into the first `j`
+    // of `y`, for every `i` and `j`.
+    let mut cost = vec![vec![0; y.len() + 1]; x.len() + 1];
+    for (j, cell) in cost[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=x.len() {
+        cost[i][0] = i;
+        for j in 1..=y.len() {
+            let change = cost[i - 1][j - 1] + (x[i - 1] != y[j - 1]) as usize;
+            cost[i][j] = change.min(cost[i - 1][j] + 1).min(cost[i][j - 1] + 1);
+        }
+        // Every way through goes through this row, so nothing's cheaper.
+        if cost[i].iter().min().is_some_and(|&least| least > k) {
+            return None;
+        }
+    }
+    let distance = cost[x.len()][y.len()];
+    if distance > k {
+        return None;
+    }
+
+    // Walk back through the cheapest edits, keeping the letters they leave
+    // alone - preferring those, when there's a choice.
+    let (mut i, mut j, mut common) = (x.len(), y.len(), Vec::new());
+    while i > 0 && j > 0 {
+        if x[i - 1] == y[j - 1] && cost[i][j] == cost[i - 1][j - 1] {
+            common.push(x[i - 1]);
+            i -= 1;
+            j -= 1;
+        } else if cost[i][j] == cost[i - 1][j - 1] + 1 {
+            i -= 1;
+            j -= 1;
+        } else if cost[i][j] == cost[i - 1][j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    Some((distance, common.into_iter().rev().collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::generate::Rng;
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    fn found(m: (usize, usize), distance: usize, common: &str) -> Match {
+        Match{ ids: m, distance, common: common.to_string() }
+    }
+
+    #[test]
+    fn hamming_distances() {
+        let ids = labels(&["abcde", "abxde", "axxde", "abcde", "zzzzz", "abc"]);
+        assert_eq!(matches(&ids, Distance::Hamming(1)), [found((0, 1), 1, "abde"), found((1, 2), 1, "axde"),
+                                                          found((1, 3), 1, "abde")]);
+        assert_eq!(matches(&ids, Distance::Hamming(2)), [found((0, 1), 1, "abde"), found((0, 2), 2, "ade"),
+                                                          found((1, 2), 1, "axde"), found((1, 3), 1, "abde"),
+                                                          found((2, 3), 2, "ade")]);
+        assert_eq!(matches(&ids, Distance::Hamming(0)), []);
+        // Further apart than they are long.
+        assert_eq!(matches(&labels(&["ab", "cd", "ab"]), Distance::Hamming(7)).len(), 2);
+    }
+
+    #[test]
+    fn edit_distances() {
+        let ids = labels(&["widget", "widgets", "wdget", "gadget", "wigdet", ""]);
+        assert_eq!(matches(&ids, Distance::Levenshtein(1)), [found((0, 1), 1, "widget"), found((0, 2), 1, "wdget")]);
+        assert_eq!(matches(&ids, Distance::Levenshtein(2)), [
+            found((0, 1), 1, "widget"), found((0, 2), 1, "wdget"), found((0, 3), 2, "dget"), found((0, 4), 2, "wiet"),
+            found((1, 2), 2, "wdget"), found((2, 3), 2, "dget"), found((2, 4), 2, "wget"),
+        ]);
+        assert_eq!(edits(&[], &['a', 'b'], 2), Some((2, String::new())));
+        assert_eq!(edits(&['a'; 10], &['b'; 10], 3), None);
+    }
+
+    #[test]
+    fn against_pairs() {
+        // Short labels from a few letters, so plenty of them match.
+        let mut rng = Rng::new(3);
+        for _ in 0..100 {
+            let ids = (0..rng.between(0, 30))
+                .map(|_| (0..rng.between(0, 6)).map(|_| *rng.pick(&['a', 'b', 'c'])).collect::<String>())
+                .collect::<Vec<_>>();
+            let chars = letters(&ids);
+            for k in 1..4 {
+                let mut expected = Vec::new();
+                for (a, x) in chars.iter().enumerate() {
+                    for (b, y) in chars.iter().enumerate().skip(a + 1) {
+                        let distance = x.iter().zip(y).filter(|(p, q)| p != q).count();
+                        if x.len() == y.len() && (1..=k).contains(&distance) {
+                            let common = x.iter().zip(y).filter(|(p, q)| p == q).map(|(p, _)| p).collect();
+                            expected.push(Match{ ids: (a, b), distance, common });
+                        }
+                    }
+                }
+                assert_eq!(matches(&ids, Distance::Hamming(k)), expected, "{} of {:?}", k, ids);
+
+                // Edits never need more than letters in place, and for one
+                // letter, equally long IDs need the same.
+                for m in matches(&ids, Distance::Levenshtein(k)) {
+                    let (x, y) = (&chars[m.ids.0], &chars[m.ids.1]);
+                    assert!(m.distance <= k && m.common.chars().count() >= x.len().max(y.len()) - m.distance);
+                }
+                let hamming = matches(&ids, Distance::Hamming(k)).into_iter().map(|m| m.ids).collect::<Vec<_>>();
+                let levenshtein = matches(&ids, Distance::Levenshtein(k)).into_iter().map(|m| m.ids).collect::<Vec<_>>();
+                assert!(hamming.iter().all(|ids| levenshtein.contains(ids)));
+                if k == 1 {
+                    assert_eq!(hamming, levenshtein.into_iter().filter(|&(a, b)| chars[a].len() == chars[b].len())
+                                                   .collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+}
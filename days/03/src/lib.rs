@@ -0,0 +1,270 @@
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{column_of, Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::grid::Grid;
+use aoc_common::parse::{self, Expected, Fail};
+
+pub struct Claim { id: u16, x: u16, y: u16, w: u16, h: u16 }
+
+impl Claim {
+    // Where the claim starts and stops, across and down.
+    fn edges(&self) -> (usize, usize, usize, usize) {
+        let (x, y, w, h) = (self.x as usize, self.y as usize, self.w as usize, self.h as usize);
+        (x, x + w, y, y + h)
+    }
+
+    // Every square inch of fabric the claim covers.
+    #[cfg(test)]
+    fn squares(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (left, right, top, bottom) = self.edges();
+        (top..bottom).flat_map(move |y| (left..right).map(move |x| (x, y)))
+    }
+
+    // Whether the two claims share a square inch.
+    fn overlaps(&self, other: &Claim) -> bool {
+        let (a, b) = (self.edges(), other.edges());
+        a.0 < b.1 && b.0 < a.1 && a.2 < b.3 && b.2 < a.3
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // The line doesn't look like `#12 @ 369,930: 21x14` at all.
+    BadClaim { pos: Pos, found: String },
+    // The claim has the right shape, but one of its numbers is enormous.
+    BadNumber { pos: Pos, found: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadClaim { pos, found } =>
+                write!(f, "{}: expected a claim like `#12 @ 369,930: 21x14`, found `{}`", pos, found),
+            ParseError::BadNumber { pos, found } =>
+                write!(f, "{}: expected a number up to {}, found `{}`", pos, u16::MAX, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::BadClaim { pos, .. } | ParseError::BadNumber { pos, .. } => *pos,
+        }
+    }
+}
+
+// A number that's too large is just that; anything else wrong means the
+// line isn't a claim at all.
+impl From<Fail<'_>> for ParseError {
+    fn from(fail: Fail) -> ParseError {
+        match fail.expected {
+            Expected::InRange => ParseError::BadNumber { pos: fail.pos, found: fail.found.to_string() },
+            _ => {
+                let claim = fail.line.trim_start();
+                ParseError::BadClaim { pos: Pos::new(fail.pos.line, column_of(fail.line, claim)),
+                                       found: claim.to_string() }
+            },
+        }
+    }
+}
+
+// Sample: #12 @ 369,930: 21x14
+pub fn parse(puzzle: &str) -> Result<Vec<Claim>, ParseError> {
+    parse::records(puzzle, |line| {
+        line.skip_while(char::is_whitespace);
+        line.literal("#")?;
+        let id = line.int()?;
+        line.literal(" @ ")?;
+        let x = line.int()?;
+        line.literal(",")?;
+        let y = line.int()?;
+        line.literal(": ")?;
+        let w = line.int()?;
+        line.literal("x")?;
+        let h = line.int()?;
+        Ok(Claim{ id, x, y, w, h })
+    })
+}
+
+// The edges of the claims, left to right (or top to bottom), each just the
+// once.
+fn cuts(edges: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut cuts = edges.collect::<Vec<_>>();
+    cuts.sort_unstable();
+    cuts.dedup();
+    cuts
+}
+
+// Lay all the claims out on the fabric, counting how many cover each piece
+// of it. The fabric is cut up along the claims' edges, rather than into
+// square inches, so a few enormous claims cost no more than a few small ones:
+// every claim covers a whole number of the pieces between the cuts.
+fn cover(claims: &[Claim]) -> (Vec<usize>, Vec<usize>, Grid<i32>) {
+    let xs = cuts(claims.iter().flat_map(|c| { let (left, right, _, _) = c.edges(); vec![left, right] }));
+    let ys = cuts(claims.iter().flat_map(|c| { let (_, _, top, bottom) = c.edges(); vec![top, bottom] }));
+    let piece = |cuts: &[usize], at| cuts.binary_search(&at).unwrap_or_else(|idx| idx);
+
+    // Mark the corners of each claim, +1 where it starts covering and -1
+    // where it stops, then add the marks up along the rows and down the
+    // columns.
+    let mut fabric = Grid::new(xs.len(), ys.len(), 0);
+    for claim in claims {
+        let (left, right, top, bottom) = claim.edges();
+        let (left, right, top, bottom) = (piece(&xs, left), piece(&xs, right), piece(&ys, top), piece(&ys, bottom));
+        fabric[(left, top)] += 1;
+        fabric[(right, top)] -= 1;
+        fabric[(left, bottom)] -= 1;
+        fabric[(right, bottom)] += 1;
+    }
+    for y in 0..ys.len() {
+        for x in 1..xs.len() {
+            fabric[(x, y)] += fabric[(x - 1, y)];
+        }
+    }
+    for y in 1..ys.len() {
+        for x in 0..xs.len() {
+            fabric[(x, y)] += fabric[(x, y - 1)];
+        }
+    }
+    (xs, ys, fabric)
+}
+
+// "How many square inches of fabric are within two or more claims?"
+pub fn part1(claims: &[Claim]) -> usize {
+    // Now simply add up all the pieces where there are more than one claim.
+    // (The last row and column are past the end of every claim.)
+    let (xs, ys, fabric) = cover(claims);
+    fabric.cells()
+        .filter(|&(_, &n)| n > 1)
+        .map(|((x, y), _)| (xs[x + 1] - xs[x]) * (ys[y + 1] - ys[y]))
+        .sum()
+}
+
+// "What is the ID of the only claim that doesn't overlap?"
+pub fn part2(claims: &[Claim]) -> Answer<u16> {
+    // The intact claim is the one that has all of its squares to itself,
+    // which is quicker to see by comparing it with every other claim than by
+    // counting its squares, when claims can be huge.
+    let intact = claims.iter().enumerate()
+        .filter(|&(idx, claim)| claims.iter().enumerate().all(|(other, c)| other == idx || !claim.overlaps(c)))
+        .map(|(_, claim)| claim)
+        .collect::<Vec<_>>();
+
+    // There must be exactly one such claim.
+    match intact.as_slice() {
+        [claim] => Ok(claim.id),
+        [] => Err(NoAnswer::new("every claim overlaps another")),
+        _ => Err(NoAnswer::new(format!("{} claims don't overlap any other", intact.len()))),
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Vec<Claim>;
+    type Config = ();
+    type Answer1 = usize;
+    type Answer2 = u16;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Vec<Claim>, ParseError> { parse(puzzle) }
+    fn part1(claims: &Vec<Claim>, _: &()) -> Answer<usize> { Ok(part1(claims)) }
+    fn part2(claims: &Vec<Claim>, _: &()) -> Answer<u16> { part2(claims) }
+}
+
+impl Generate for Day03 {
+    const SIZE: usize = 1400;
+
+    // `size` claims on the 1000 inch square of fabric, each overlapping
+    // another, apart from the one claim that's kept clear of all the rest.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const FABRIC: i64 = 1000;
+        // (x, y, w, h)
+        let random = |rng: &mut Rng| {
+            let (w, h) = (rng.between(1, 30), rng.between(1, 30));
+            (rng.between(0, FABRIC - w), rng.between(0, FABRIC - h), w, h)
+        };
+        let overlap = |a: (i64, i64, i64, i64), b: (i64, i64, i64, i64)|
+            a.0 < b.0 + b.2 && b.0 < a.0 + a.2 && a.1 < b.1 + b.3 && b.1 < a.1 + a.3;
+
+        let size = size.max(3);
+        let alone = random(rng);
+        let mut claims = Vec::with_capacity(size);
+        while claims.len() < size - 1 {
+            let (mut x, mut y, w, h) = random(rng);
+            // Cover one of the squares of an earlier claim.
+            if !claims.is_empty() {
+                let (ox, oy, ow, oh) = *rng.pick(&claims);
+                x = (rng.between(ox, ox + ow - 1) - rng.between(0, w - 1)).clamp(0, FABRIC - w);
+                y = (rng.between(oy, oy + oh - 1) - rng.between(0, h - 1)).clamp(0, FABRIC - h);
+            }
+            if !overlap((x, y, w, h), alone) {
+                claims.push((x, y, w, h));
+            }
+        }
+        claims.insert(rng.below(size), alone);
+
+        claims.iter().enumerate()
+            .map(|(idx, (x, y, w, h))| format!("#{} @ {},{}: {}x{}\n", idx + 1, x, y, w, h))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    aoc_common::examples!(Day03);
+    aoc_common::generated!(Day03, 30);
+
+    #[test]
+    fn bad_claims() {
+        assert_eq!(parse("#1 @ 1,3: 4x4\n#2 @ 3,1 4x4").err(),
+                   Some(ParseError::BadClaim { pos: Pos::new(2, 1), found: String::from("#2 @ 3,1 4x4") }));
+        assert_eq!(parse("#1 @ 1,99999: 4x4").err(),
+                   Some(ParseError::BadNumber { pos: Pos::new(1, 8), found: String::from("99999") }));
+    }
+
+    #[test]
+    fn huge_claims() {
+        let claims = parse("#1 @ 65535,65535: 65535x65535\n#2 @ 0,0: 2x2\n#3 @ 1,1: 2x2\n#4 @ 0,1: 1x1").unwrap();
+        assert_eq!((part1(&claims), part2(&claims)), (2, Ok(1)));
+        assert!(part2(&claims[1..]).is_err());
+        assert!(part2(&claims[..2]).is_err());
+    }
+
+    #[test]
+    fn against_reference() {
+        // Compare every pair of claims, square by square.
+        for (seed, claims) in aoc_common::generate::inputs::<Day03>(30, 30) {
+            let mut overlaps = HashSet::new();
+            let mut intact = Vec::new();
+            for a in &claims {
+                let mut alone = true;
+                for b in claims.iter().filter(|b| b.id != a.id) {
+                    let inside = |&(x, y): &(usize, usize)| (b.x as usize..(b.x + b.w) as usize).contains(&x)
+                                                         && (b.y as usize..(b.y + b.h) as usize).contains(&y);
+                    for at in a.squares().filter(inside) {
+                        overlaps.insert(at);
+                        alone = false;
+                    }
+                }
+                if alone {
+                    intact.push(a.id);
+                }
+            }
+            assert_eq!(part1(&claims), overlaps.len(), "seed {}", seed);
+            assert_eq!(part2(&claims), Ok(intact[0]), "seed {}", seed);
+            assert_eq!(intact.len(), 1, "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::{self, Cursor, Expected, Fail};
+
+/* "Oh no... dates..." */
+
+enum Event {
+    Begin(u16),
+    Sleep,
+    Wake,
+}
+
+pub struct Record {
+    dt: NaiveDateTime,
+    action: Event,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // Some fixed piece of the record - a bracket, or what the guard did.
+    Expected { pos: Pos, expected: Expected, found: String },
+    // The `[1518-11-01 00:00]` stamp isn't a real date and time.
+    BadTimestamp { pos: Pos, found: String },
+    // The guard on shift should be `#` and a number.
+    BadGuard { pos: Pos, found: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Expected { pos, expected, found } =>
+                write!(f, "{}: expected {}, found `{}`", pos, expected, found),
+            ParseError::BadTimestamp { pos, found } =>
+                write!(f, "{}: expected a timestamp like `1518-11-01 00:00`, found `{}`", pos, found),
+            ParseError::BadGuard { pos, found } =>
+                write!(f, "{}: expected a guard like `#10`, found `{}`", pos, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::Expected { pos, .. }
+            | ParseError::BadTimestamp { pos, .. }
+            | ParseError::BadGuard { pos, .. } => *pos,
+        }
+    }
+}
+
+impl From<Fail<'_>> for ParseError {
+    fn from(fail: Fail) -> ParseError {
+        ParseError::Expected { pos: fail.pos, expected: fail.expected, found: fail.found.to_string() }
+    }
+}
+
+// Sample: 1518-11-01 00:00
+fn timestamp<'a>(c: &mut Cursor<'a>) -> Result<NaiveDateTime, Fail<'a>> {
+    let start = *c;
+    let year = c.int()?;
+    c.literal("-")?;
+    let month = c.int()?;
+    c.literal("-")?;
+    let day = c.int()?;
+    c.literal(" ")?;
+    let hour = c.int()?;
+    c.literal(":")?;
+    let min = c.int()?;
+    NaiveDate::from_ymd_opt(year, month, day).and_then(|date| date.and_hms_opt(hour, min, 0))
+        .ok_or_else(|| start.fail(Expected::Thing("a real date")))
+}
+
+// Sample: [1518-11-01 00:00] Guard #10 begins shift
+fn parse_line(line: &mut Cursor) -> Result<Record, ParseError> {
+    line.literal("[")?;
+    let dt = line.take_while(|c| c != ']').all(Expected::Thing("a timestamp"), timestamp)
+        .map_err(|fail| ParseError::BadTimestamp { pos: fail.pos, found: fail.found.to_string() })?;
+    line.literal("] ")?;
+
+    let action = if line.eat("falls asleep") {
+        Event::Sleep
+    } else if line.eat("wakes up") {
+        Event::Wake
+    } else if line.eat("Guard ") {
+        let guard = line.take_while(|c| c != ' ');
+        let id = guard.all(Expected::Thing("a guard"), |g| { g.literal("#")?; g.int() })
+            .map_err(|fail| ParseError::BadGuard { pos: fail.pos, found: fail.found.to_string() })?;
+        line.literal(" begins shift")?;
+        Event::Begin(id)
+    } else {
+        return Err(line.fail(Expected::Thing("`falls asleep`, `wakes up` or `Guard #N begins shift`")).into());
+    };
+    Ok(Record{ dt, action })
+}
+
+// parse the input puzzle, ensuring chronological ordering
+pub fn parse(puzzle: &str) -> Result<Vec<Record>, ParseError> {
+    let mut records = parse::records(puzzle, parse_line)?;
+    records.sort_unstable_by_key(|a| a.dt);
+    Ok(records)
+}
+
+type Schedule = HashMap<u16, [u32; 60]>;
+
+// Builds every guard's schedule.
+// Like the table in the description, but with schedules grouped by
+// guard id and minute. Counts the overlapping minutes into an array,
+// discarding the rest of the date information.
+//
+// So it looks like: {#99: [0,0,0,1,1,1,2,2,1,0,0,...], #100: [3,2,2,1,1,0,0...], }
+//
+// Records that make no sense in order - a nap with no guard on shift, or
+// waking up without having fallen asleep - mean there's no telling who slept
+// when.
+fn create_guard_schedule(records: &[Record]) -> Answer<Schedule> {
+    let mut schedule = HashMap::new();
+
+    struct State { guard: Option<u16>, sleep_start: Option<usize> }
+    let init = State{ guard: None, sleep_start: None };
+
+    records.iter().try_fold(init, |mut state, rec| {
+        let now_minute = rec.dt.minute() as usize;
+
+        match (&rec.action, state.guard) {
+            (Event::Begin(guard), _) => state = State{ guard: Some(*guard), sleep_start: None },
+            (_, None) => return Err(NoAnswer::new(format!("nobody is on shift at {}", rec.dt))),
+            (Event::Sleep, _) => { state.sleep_start = Some(now_minute) },
+            (Event::Wake, Some(guard)) => {
+                let sleep_start = state.sleep_start.take().filter(|&start| start <= now_minute).ok_or_else(||
+                    NoAnswer::new(format!("guard #{} wakes up at {} without having fallen asleep", guard, rec.dt)))?;
+                let timetable = schedule.entry(guard).or_insert([0; 60]);
+                for min in &mut timetable[sleep_start..now_minute] {
+                    *min += 1;
+                }
+            },
+        };
+        Ok(state)
+    })?;
+    Ok(schedule)
+}
+
+// Perform the specified strategy to find the ideal guard, and minute to break in!
+// Function takes a closure that is used with `max_by_key` over all schedule time-tables.
+// Ties go to the lowest guard id, and the earliest minute - the map's order
+// isn't any order at all.
+fn execute(schedule: &Schedule, strategy: impl Fn(&[u32]) -> u32) -> Answer<u32> {
+    // Identify the guard to target using the strategy provided.
+    let (id, sleep_minutes_vec) = schedule.iter()
+        .max_by_key(|(id, a)| (strategy(&a[..]), Reverse(**id)))
+        .ok_or_else(|| NoAnswer::new("no guard ever falls asleep"))?;
+
+    // Now, take that guard's time-table and find the minute to strike.
+    let sleepiest_minute = sleep_minutes_vec.iter().enumerate()
+        .max_by_key(|&(minute, n)| (n, Reverse(minute)))
+        .unwrap().0;
+
+    Ok(*id as u32 * sleepiest_minute as u32)
+}
+
+// "Find the guard that has the most minutes asleep. What minute does that guard spend asleep the most?"
+pub fn part1(records: &[Record]) -> Answer<u32> {
+    let schedule = create_guard_schedule(records)?;
+
+    fn total_sleep(v: &[u32]) -> u32 { v.iter().sum() }
+
+    execute(&schedule, total_sleep)
+}
+
+// "Of all guards, which guard is most frequently asleep on the same minute?"
+pub fn part2(records: &[Record]) -> Answer<u32> {
+    let schedule = create_guard_schedule(records)?;
+
+    fn heaviest_sleep(v: &[u32]) -> u32 { *v.iter().max().unwrap() }
+
+    execute(&schedule, heaviest_sleep)
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<Record>;
+    type Config = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Vec<Record>, ParseError> { parse(puzzle) }
+    fn part1(records: &Vec<Record>, _: &()) -> Answer<u32> { part1(records) }
+    fn part2(records: &Vec<Record>, _: &()) -> Answer<u32> { part2(records) }
+}
+
+impl Generate for Day04 {
+    const SIZE: usize = 350;
+
+    // `size` shifts, one a night, by a rota of guards who nap a few times a
+    // shift (or not at all). The records come out of order, as they do in
+    // the real thing.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let guards = (0..size / 15 + 2).map(|_| rng.between(10, 3500)).collect::<Vec<_>>();
+        let first = NaiveDate::from_ymd_opt(1518, 1, 1).unwrap();
+
+        let mut records = Vec::new();
+        for night in 0..size.max(1) {
+            let date = first + chrono::Duration::days(night as i64);
+            let guard = rng.pick(&guards);
+            records.push(if rng.chance(0.5) {
+                format!("[{} 23:{:02}] Guard #{} begins shift", date.pred_opt().unwrap(), rng.between(45, 59), guard)
+            } else {
+                format!("[{} 00:{:02}] Guard #{} begins shift", date, rng.between(0, 3), guard)
+            });
+
+            // Nap from one minute to the next, so an even number of them.
+            let naps = if night == 0 { rng.between(1, 3) } else { rng.between(0, 3) };
+            let mut minutes = Vec::new();
+            while minutes.len() < 2 * naps as usize {
+                let minute = rng.between(4, 59);
+                if !minutes.contains(&minute) {
+                    minutes.push(minute);
+                }
+            }
+            minutes.sort_unstable();
+            for (n, minute) in minutes.iter().enumerate() {
+                let action = if n % 2 == 0 { "falls asleep" } else { "wakes up" };
+                records.push(format!("[{} 00:{:02}] {}", date, minute, action));
+            }
+        }
+        rng.shuffle(&mut records);
+        records.iter().map(|record| format!("{}\n", record)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::examples!(Day04);
+    aoc_common::generated!(Day04, 30);
+
+    #[test]
+    fn bad_records() {
+        let err = |line| parse(line).err().unwrap();
+        assert_eq!(err("[1518-13-01 00:00] wakes up"),
+                   ParseError::BadTimestamp { pos: Pos::new(1, 2), found: String::from("1518-13-01 00:00") });
+        assert_eq!(err("[1518-11-01 00:00] wakes up\n[1518-11"),
+                   ParseError::BadTimestamp { pos: Pos::new(2, 2), found: String::from("1518-11") });
+        assert_eq!(err("[1518-11-01 00:00] Guard #ten begins shift").pos(), Pos::new(1, 26));
+        assert_eq!(err("[1518-11-01 00:00] dozes off").pos(), Pos::new(1, 20));
+        assert_eq!(err("1518-11-01 00:00] wakes up").pos(), Pos::new(1, 1));
+    }
+
+    #[test]
+    fn nonsense() {
+        let records = |lines: &[&str]| parse(&lines.join("\n")).unwrap();
+        let shift = "[1518-11-01 00:00] Guard #10 begins shift";
+        assert!(part1(&records(&[])).is_err());
+        assert!(part2(&records(&[shift])).is_err());
+        assert!(part1(&records(&["[1518-11-01 00:05] falls asleep", "[1518-11-01 00:25] wakes up"])).is_err());
+        assert!(part1(&records(&[shift, "[1518-11-01 00:25] wakes up"])).is_err());
+        assert!(part1(&records(&[shift, "[1518-11-01 00:25] falls asleep", "[1518-11-02 00:05] wakes up"])).is_err());
+        assert_eq!(part2(&records(&[shift, "[1518-11-01 00:05] falls asleep", "[1518-11-01 00:25] wakes up"])), Ok(50));
+    }
+
+    #[test]
+    fn against_reference() {
+        for (seed, records) in aoc_common::generate::inputs::<Day04>(30, 40) {
+            // Every nap as (guard, from, to), and every guard who took one.
+            let mut naps = Vec::new();
+            let (mut guard, mut from) = (0, 0);
+            for record in &records {
+                match record.action {
+                    Event::Begin(id) => guard = id,
+                    Event::Sleep => from = record.dt.minute(),
+                    Event::Wake => naps.push((guard, from, record.dt.minute())),
+                }
+            }
+            let mut guards = naps.iter().map(|nap| nap.0).collect::<Vec<_>>();
+            guards.sort_unstable();
+            guards.dedup();
+
+            // How many naps a guard took over a minute.
+            let asleep = |guard, minute| naps.iter()
+                .filter(|&&(id, from, to)| id == guard && from <= minute && minute < to)
+                .count() as u32;
+            // The guard with the most `strategy`, and the minute they're most
+            // often asleep, the first of each that's best.
+            let choose = |strategy: &dyn Fn(u16) -> u32| {
+                let mut best = guards[0];
+                for &id in &guards {
+                    if strategy(id) > strategy(best) {
+                        best = id;
+                    }
+                }
+                let mut minute = 0;
+                for m in 0..60 {
+                    if asleep(best, m) > asleep(best, minute) {
+                        minute = m;
+                    }
+                }
+                best as u32 * minute
+            };
+
+            assert_eq!(part1(&records), Ok(choose(&|id| (0..60).map(|m| asleep(id, m)).sum())), "seed {}", seed);
+            assert_eq!(part2(&records), Ok(choose(&|id| (0..60).map(|m| asleep(id, m)).max().unwrap())), "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::Cursor;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // Units are letters, and nothing else.
+    BadUnit { pos: Pos, found: char },
+    // There's nothing to react!
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadUnit { pos, found } =>
+                write!(f, "{}: expected a polymer unit (a letter), found `{}`", pos, found.escape_default()),
+            ParseError::Empty => write!(f, "{}: expected a polymer, found nothing", self.pos()),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::BadUnit { pos, .. } => *pos,
+            ParseError::Empty => Pos::new(1, 1),
+        }
+    }
+}
+
+pub fn parse(puzzle: &str) -> Result<Vec<u8>, ParseError> {
+    let mut c = Cursor::new(puzzle);
+    let polymer = c.take_while(|unit| unit.is_ascii_alphabetic());
+    // NEWLINE, MY ARCH NEMESIS! (`end` lets trailing whitespace go.)
+    c.end().map_err(|fail| ParseError::BadUnit { pos: fail.pos, found: c.peek().unwrap_or('\n') })?;
+    if polymer.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(polymer.rest().as_bytes().to_vec())
+}
+
+// Walk the polymer string, comparing diagraphs to find polarity shifts and remove them.
+fn react_polymer(polymer: &[u8]) -> VecDeque<u8> {
+    let mut q = Vec::from(polymer);
+
+    // Stack of "units" build up in `acc`
+    let mut acc = VecDeque::new();
+
+    while let Some(a) = q.pop() {
+
+        /* Conditional avoids nested if blocks, to satisfy BC involving `acc` */
+
+        let mut is_dupe = false;
+
+        if let Some(b) = acc.back() {
+            // 'a' XOR 'A' is 32, which holds for all letters
+            is_dupe = a ^ b == 32
+        }
+
+        if is_dupe {
+            acc.pop_back();
+        } else {
+            acc.push_back(a);
+        }
+    }
+    acc
+}
+
+// "How many units remain after fully reacting the polymer you scanned?"
+pub fn part1(puzzle: &[u8]) -> usize {
+    react_polymer(puzzle).len()
+}
+
+// "What is the length of the shortest polymer you can produce by removing all units of exactly one type and fully reacting the result?"
+pub fn part2(puzzle: &[u8]) -> usize {
+    // Do a first pass - which trims the string down significantly.
+    let puzzle = react_polymer(puzzle);
+
+    let mut shortest = usize::MAX;
+
+    // For such long input, we can just assume every character is present
+    // somewhere and brute force attempt each as a candidate.
+    for ch in b'a'..=b'z' {
+        let trimmed: Vec<u8> = puzzle.iter()
+            .filter(|&&c| c.to_ascii_lowercase() != ch)
+            .copied()
+            .collect();
+
+        let size = react_polymer(&trimmed).len();
+        if size < shortest {
+            shortest = size;
+        }
+    }
+    shortest
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Vec<u8>;
+    type Config = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Vec<u8>, ParseError> { parse(puzzle) }
+    fn part1(polymer: &Vec<u8>, _: &()) -> Answer<usize> { Ok(part1(polymer)) }
+    fn part2(polymer: &Vec<u8>, _: &()) -> Answer<usize> { Ok(part2(polymer)) }
+}
+
+impl Generate for Day05 {
+    const SIZE: usize = 50_000;
+
+    // A polymer of `size` units, with reactions nested inside one another
+    // (like `cBbC`) as well as units that are left over.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut polymer = String::with_capacity(size);
+        let mut open = Vec::new();
+        while polymer.len() < size {
+            let unit = match open.last() {
+                Some(&unit) if rng.chance(0.45) => {
+                    open.pop();
+                    unit ^ 0x20
+                },
+                _ => {
+                    let unit = if rng.chance(0.5) { b'a' } else { b'A' } + rng.below(26) as u8;
+                    open.push(unit);
+                    unit
+                },
+            };
+            polymer.push(unit as char);
+        }
+        polymer + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::generated!(Day05, 200);
+
+    static TEST: &[u8] = b"dabAcCaCBAcCcaDA";
+    #[test]
+    fn example_1() {
+        let expect = 10; // Ten remaining units in the polymer.
+        assert_eq!(part1(TEST), expect);
+    }
+
+    #[test]
+    fn example_2() {
+        // Removing all the 'c/C' instances and reacting the polymer leaves 4 units.
+        let expect = 4;
+        assert_eq!(part2(TEST), expect);
+    }
+
+    #[test]
+    fn bad_polymer() {
+        assert_eq!(parse("dabAc\n"), Ok(b"dabAc".to_vec()));
+        assert_eq!(parse("dab Ac"), Err(ParseError::BadUnit { pos: Pos::new(1, 4), found: ' ' }));
+        assert_eq!(parse("dab\nAc"), Err(ParseError::BadUnit { pos: Pos::new(1, 4), found: '\n' }));
+        assert_eq!(parse("\n"), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn reacts_away() {
+        assert_eq!((part1(b"aA"), part2(b"aA")), (0, 0));
+    }
+
+    // React the polymer the way the puzzle describes it: find a pair that
+    // reacts, take it out, and start again from the beginning.
+    fn react(polymer: &[u8]) -> usize {
+        let mut polymer = polymer.to_vec();
+        while let Some(i) = (1..polymer.len()).find(|&i| polymer[i - 1] != polymer[i]
+                                                    && polymer[i - 1].eq_ignore_ascii_case(&polymer[i])) {
+            polymer.drain(i - 1..=i);
+        }
+        polymer.len()
+    }
+
+    #[test]
+    fn against_reference() {
+        for (seed, polymer) in aoc_common::generate::inputs::<Day05>(30, 150) {
+            assert_eq!(part1(&polymer), react(&polymer), "seed {}", seed);
+            let shortest = (b'a'..=b'z').map(|unit| {
+                react(&polymer.iter().copied().filter(|c| c.to_ascii_lowercase() != unit).collect::<Vec<_>>())
+            }).min();
+            assert_eq!(Some(part2(&polymer)), shortest, "seed {}", seed);
+        }
+    }
+}
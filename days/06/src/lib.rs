@@ -0,0 +1,319 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::grid::Grid;
+use aoc_common::parse::{self, Cursor, Expected, Fail};
+use aoc_common::point::{Dir, Point};
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // Coordinates are written `x, y`.
+    MissingSeparator { pos: Pos, found: String },
+    // Coordinates live on the grid, so they can't be negative - or so far
+    // out that the grid is too big to look at.
+    BadCoordinate { pos: Pos, found: String },
+    // Without any coordinates, there are no areas to measure.
+    Empty,
+    // Every coordinate gets a letter of its own (well, a `u8`).
+    TooMany { pos: Pos },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingSeparator { pos, found } =>
+                write!(f, "{}: expected coordinates like `1, 6`, found `{}`", pos, found),
+            ParseError::BadCoordinate { pos, found } =>
+                write!(f, "{}: expected a number from 0 to {}, found `{}`", pos, MAX_COORDINATE, found),
+            ParseError::Empty => write!(f, "{}: expected coordinates, found nothing", self.pos()),
+            ParseError::TooMany { pos } =>
+                write!(f, "{}: expected at most {} coordinates, found more", pos, MAX_COORDINATES),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::MissingSeparator { pos, .. }
+            | ParseError::BadCoordinate { pos, .. }
+            | ParseError::TooMany { pos } => *pos,
+            ParseError::Empty => Pos::new(1, 1),
+        }
+    }
+}
+
+// Anything other than the separator going wrong is a bad coordinate.
+impl From<Fail<'_>> for ParseError {
+    fn from(fail: Fail) -> ParseError {
+        match fail.expected {
+            Expected::Literal(_) => ParseError::MissingSeparator { pos: Pos::new(fail.pos.line, 1),
+                                                                   found: fail.line.to_string() },
+            _ => ParseError::BadCoordinate { pos: fail.pos, found: fail.found.to_string() },
+        }
+    }
+}
+
+// Every location on the grid gets compared with every coordinate, so both
+// have to stay about the size of the real thing: a few hundred across, and
+// fifty coordinates.
+const MAX_COORDINATE: i16 = 999;
+const MAX_COORDINATES: usize = 256;
+
+// Coordinates live on the grid, so they can't be negative.
+fn coordinate<'a>(c: &mut Cursor<'a>) -> Result<i16, Fail<'a>> {
+    c.check(Expected::Thing("a number from 0 to 999"), Cursor::int, |&n| (0..=MAX_COORDINATE).contains(&n))
+}
+
+// Sample: 1, 6
+pub fn parse(puzzle: &str) -> Result<Vec<Point<i16>>, ParseError> {
+    let coords = parse::records(puzzle, |line| -> Result<_, ParseError> {
+        let x = coordinate(line)?;
+        line.literal(", ")?;
+        Ok(Point::new(x, coordinate(line)?))
+    })?;
+    if coords.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    if coords.len() > MAX_COORDINATES {
+        return Err(ParseError::TooMany { pos: Pos::new(MAX_COORDINATES + 1, 1) });
+    }
+    Ok(coords)
+}
+
+fn find_bounds(coords: &[Point<i16>]) -> (Point<i16>, Point<i16>) {
+    (Point::new(0,0), // Assume 0,0 for left,top boundary
+     Point::new(coords.iter().max_by_key(|c| c.x).unwrap().x+1,  // right edge
+                coords.iter().max_by_key(|c| c.y).unwrap().y+1)) // bottom edge
+}
+
+fn new_grid(maxs: Point<i16>) -> Grid<Option<u8>> {
+    Grid::new(maxs.x as usize, maxs.y as usize, None)
+}
+
+fn debug_show_grid(grid: &Grid<Option<u8>>) {
+    if !log::log_enabled!(log::Level::Debug) { return }
+
+    log::debug!("\n{}", grid.render(|c| c.map_or('.', |c| std::char::from_u32('A' as u32 + c as u32).unwrap())));
+}
+
+// "What is the size of the largest area that isn't infinite?"
+pub fn part1(coords: &[Point<i16>]) -> Answer<u32> {
+    // Find four boundaries
+    let (mins, maxs) = find_bounds(coords);
+
+    // Create a grid with the above boundaries.
+    let mut grid = new_grid(maxs);
+    // Track region sizes and if a region is infinite.
+    let mut counts = vec![0u32; coords.len()];
+    let mut infinites = HashSet::new();
+
+    // Loop over every location in the grid
+    for y in mins.y..maxs.y {
+        for x in mins.x..maxs.x {
+            let loc = Point::new(x, y);
+
+            let (mut neighbor, mut min_dist) = (None, i16::MAX);
+
+            // Find the closest coordinate - ties mean no one wins
+            for (idx, point) in coords.iter().enumerate() {
+                let dist = loc.manhattan(*point);
+                if dist < min_dist {
+                    neighbor = Some(idx as u8);
+                    min_dist = dist;
+                } else if dist == min_dist {
+                    neighbor = None;
+                };
+            }
+            if let Some(neighbor) = neighbor {
+                // check if this location makes a coordinate 'infinite'
+                if grid.is_edge((x as usize, y as usize)) {
+                    infinites.insert(neighbor);
+                }
+
+                counts[neighbor as usize] += 1;
+            }
+            grid[(x as usize, y as usize)] = neighbor;
+        }
+    }
+    debug_show_grid(&grid);
+
+    counts.into_iter()
+        .enumerate()
+        .filter_map(|(id,size)| if infinites.contains(&(id as u8)) { None } else { Some(size) })
+        .max().ok_or_else(|| NoAnswer::new("every area is infinite"))
+}
+
+// "What is the size of the region containing all locations which have a total distance
+//  to all given coordinates of less than 10000?"
+pub fn part2(coords: &[Point<i16>], flags: &Flags) -> u32 {
+    let (mins, maxs) = find_bounds(coords);
+    // The region can reach past the coordinates: but not by `margin`, where
+    // every one of them is at least that far away, and the total too much.
+    let margin = flags.threshold.max(0) / coords.len() as i32;
+
+    // A total distance is the total across plus the total down, and those
+    // can be worked out for each column and row on their own.
+    let totals = |lo: i16, hi: i16, axis: fn(&Point<i16>) -> i16| {
+        (lo as i32 - margin..hi as i32 + margin)
+            .map(|v| coords.iter().map(|c| (v - axis(c) as i32).abs()).sum::<i32>())
+            .collect::<Vec<_>>()
+    };
+    let across = totals(mins.x, maxs.x, |c| c.x);
+    let down = totals(mins.y, maxs.y, |c| c.y);
+
+    if log::log_enabled!(log::Level::Debug) {
+        // Just the part of the region within the coordinates' bounds.
+        let mut grid = new_grid(maxs);
+        for y in mins.y..maxs.y {
+            for x in mins.x..maxs.x {
+                let (col, row) = ((x - mins.x) as i32 + margin, (y - mins.y) as i32 + margin);
+                if across[col as usize] + down[row as usize] < flags.threshold {
+                    grid[(x as usize, y as usize)] = Some(0);
+                }
+            }
+        }
+        debug_show_grid(&grid);
+    }
+
+    // Then for every column, count the rows close enough to go with it.
+    // The 'region' here is guaranteed to be one contiguous blob, so counting
+    // is all there is to it.
+    let mut down = down;
+    down.sort_unstable();
+    across.iter().map(|a| down.partition_point(|d| a + d < flags.threshold) as u32).sum()
+}
+
+// The puzzle's knobs, like part2's distance threshold.
+#[derive(Copy,Clone)]
+pub struct Flags {
+    pub threshold: i32,
+}
+
+impl Default for Flags {
+    fn default() -> Flags {
+        Flags{ threshold: 10_000 }
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Vec<Point<i16>>;
+    type Config = Flags;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Vec<Point<i16>>, ParseError> { parse(puzzle) }
+    fn part1(coords: &Vec<Point<i16>>, _: &Flags) -> Answer<u32> { part1(coords) }
+    fn part2(coords: &Vec<Point<i16>>, flags: &Flags) -> Answer<u32> { Ok(part2(coords, flags)) }
+}
+
+impl Generate for Day06 {
+    const SIZE: usize = 50;
+
+    // `size` coordinates, scattered about like the real ones are. Four of
+    // them are set around a fifth, in a diamond, to be sure of at least one
+    // finite area: nowhere outside the diamond is closer to the middle one.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let (d, size) = (rng.between(2, 30) as i16, size.max(5));
+        let mid = Point::new(rng.between(40 + d as i64, 360 - d as i64) as i16,
+                             rng.between(40 + d as i64, 360 - d as i64) as i16);
+        let mut coords = vec![mid];
+        for dir in Dir::ALL.iter() {
+            let step = mid.step(*dir).unwrap() - mid;
+            coords.push(mid + step * d);
+        }
+        while coords.len() < size {
+            let coord = Point::new(rng.between(40, 360) as i16, rng.between(40, 360) as i16);
+            if !coords.contains(&coord) {
+                coords.push(coord);
+            }
+        }
+        rng.shuffle(&mut coords);
+        coords.iter().map(|c| format!("{}, {}\n", c.x, c.y)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example region is everywhere within a total distance of 32.
+    aoc_common::examples!(Day06, Flags{ threshold: 32 });
+    aoc_common::generated!(Day06, 12);
+
+    #[test]
+    fn bad_coordinates() {
+        assert_eq!(parse("1, 1\n1 6").err(),
+                   Some(ParseError::MissingSeparator { pos: Pos::new(2, 1), found: String::from("1 6") }));
+        assert_eq!(parse("1, 1\n1, -6").err(),
+                   Some(ParseError::BadCoordinate { pos: Pos::new(2, 4), found: String::from("-6") }));
+        assert_eq!(parse("").err(), Some(ParseError::Empty));
+        assert_eq!(parse("1, 1\n6, 1000").err(),
+                   Some(ParseError::BadCoordinate { pos: Pos::new(2, 4), found: String::from("1000") }));
+        assert_eq!(parse(&"1, 1\n".repeat(257)).err(), Some(ParseError::TooMany { pos: Pos::new(257, 1) }));
+        assert!(parse(&"1, 1\n".repeat(256)).is_ok());
+    }
+
+    #[test]
+    fn all_infinite() {
+        assert!(part1(&parse("1, 1").unwrap()).is_err());
+        assert!(part1(&parse("1, 1\n5, 5\n9, 1").unwrap()).is_err());
+    }
+
+    // The size of each coordinate's area, out to `grow` past the
+    // coordinates' bounds.
+    fn areas(coords: &[Point<i16>], grow: i16) -> Vec<u32> {
+        let (lo_x, hi_x) = (coords.iter().map(|c| c.x).min().unwrap(), coords.iter().map(|c| c.x).max().unwrap());
+        let (lo_y, hi_y) = (coords.iter().map(|c| c.y).min().unwrap(), coords.iter().map(|c| c.y).max().unwrap());
+        let mut areas = vec![0; coords.len()];
+        for y in lo_y - grow..=hi_y + grow {
+            for x in lo_x - grow..=hi_x + grow {
+                let dists = coords.iter().map(|c| c.manhattan(Point::new(x, y))).collect::<Vec<_>>();
+                let nearest = *dists.iter().min().unwrap();
+                if dists.iter().filter(|&&d| d == nearest).count() == 1 {
+                    areas[dists.iter().position(|&d| d == nearest).unwrap()] += 1;
+                }
+            }
+        }
+        areas
+    }
+
+    #[test]
+    fn against_reference() {
+        for (seed, coords) in aoc_common::generate::inputs::<Day06>(8, 12) {
+            // The infinite areas are the ones that keep growing along with
+            // the space they're counted in.
+            let (near, far) = (areas(&coords, 1), areas(&coords, 2));
+            let largest = near.iter().zip(&far).filter(|(a, b)| a == b).map(|(a, _)| *a).max();
+            assert_eq!(part1(&coords).ok(), largest, "seed {}", seed);
+        }
+
+        // Just the diamond of coordinates, so the region spills well past them.
+        for (seed, coords) in aoc_common::generate::inputs::<Day06>(8, 5) {
+            // Nowhere further than the threshold from any one coordinate can
+            // be in the region, so look everywhere closer than that.
+            let threshold = 100 + 50 * seed as i32;
+            let (cx, cy) = (coords[0].x as i32, coords[0].y as i32);
+            let mut region = 0;
+            for y in cy - threshold..=cy + threshold {
+                for x in cx - threshold..=cx + threshold {
+                    let total = coords.iter().map(|c| (x - c.x as i32).abs() + (y - c.y as i32).abs()).sum::<i32>();
+                    if total < threshold {
+                        region += 1;
+                    }
+                }
+            }
+            assert_eq!(part2(&coords, &Flags{ threshold }), region, "seed {}", seed);
+        }
+    }
+}
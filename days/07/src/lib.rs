@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::heap::MinHeap;
+use aoc_common::parse::{self, Cursor, Expected, Fail};
+
+pub struct Flags { pub nworkers: usize, pub time_offset: u32 }
+struct Output { order_of_steps: String, time_taken: u32 }
+
+impl Default for Flags {
+    fn default() -> Flags {
+        Flags{ nworkers: 5, time_offset: 60 }
+    }
+}
+
+// "In what order should the steps in your instructions be completed?"
+pub fn part1(digraphs: &[(char, char)]) -> Answer<String> {
+    Ok(assemble_sleigh(digraphs, &Flags{ nworkers: 1, time_offset: 0})?.order_of_steps)
+}
+
+// "With 5 workers and the 60+ second step durations described above,
+//  how long will it take to complete all of the steps?"
+pub fn part2(digraphs: &[(char, char)], flags: &Flags) -> Answer<u32> {
+    Ok(assemble_sleigh(digraphs, flags)?.time_taken)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // One of the fixed phrases around the steps, like ` can begin.`
+    Expected { pos: Pos, expected: &'static str, found: String },
+    // Steps are named by single capital letters.
+    BadStep { pos: Pos, found: String },
+    // Each instruction gets a line to itself.
+    Trailing { pos: Pos, found: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Expected { pos, expected, found } =>
+                write!(f, "{}: expected `{}`, found `{}`", pos, expected, found),
+            ParseError::BadStep { pos, found } =>
+                write!(f, "{}: expected a step from `A` to `Z`, found `{}`", pos, found),
+            ParseError::Trailing { pos, found } =>
+                write!(f, "{}: expected the end of the line, found `{}`", pos, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::Expected { pos, .. }
+            | ParseError::BadStep { pos, .. }
+            | ParseError::Trailing { pos, .. } => *pos,
+        }
+    }
+}
+
+impl From<Fail<'_>> for ParseError {
+    fn from(fail: Fail) -> ParseError {
+        let (pos, found) = (fail.pos, fail.found.to_string());
+        match fail.expected {
+            Expected::Literal(expected) => ParseError::Expected { pos, expected, found },
+            Expected::End => ParseError::Trailing { pos, found },
+            _ => ParseError::BadStep { pos, found },
+        }
+    }
+}
+
+fn step<'a>(c: &mut Cursor<'a>) -> Result<char, Fail<'a>> {
+    c.char_if("a step", |c| c.is_ascii_uppercase())
+}
+
+// Sample: "Step C must be finished before step A can begin."
+pub fn parse(puzzle: &str) -> Result<Vec<(char, char)>, ParseError> {
+    parse::records(puzzle, |line| -> Result<_, ParseError> {
+        line.literal("Step ")?;
+        let before = step(line)?;
+        line.literal(" must be finished before step ")?;
+        let after = step(line)?;
+        line.literal(" can begin.")?;
+        Ok((before, after))
+    })
+}
+
+// Walks the graph formed from the instructions in lexical order.
+// Flags can be provided to simulate concurrent worker elves.
+fn assemble_sleigh(digraphs: &[(char, char)], flags: &Flags) -> Answer<Output> {
+    if flags.nworkers == 0 {
+        return Err(NoAnswer::new("there are no workers to do the steps"));
+    }
+    let (mut edges, mut indegrees) =
+        digraphs.iter()
+        // Using the 2char digraphs, build adjacency map & in-degree map
+        .fold((HashMap::<char, MinHeap<char>>::new(), HashMap::<char,u32>::new()),
+        |(mut edges, mut indegrees), &(v,w)| {
+            edges.entry(v).or_default().push(w);
+            *indegrees.entry(w).or_default() += 1;
+            (edges, indegrees)
+        });
+
+    // Collect all vertecies w/ no dependencies (they aren't present within `indegrees`)
+    // and keep them sorted lexographically.
+    let mut queue = {
+        let xs = edges.keys().collect::<HashSet<_>>();
+        let ys = indegrees.keys().collect::<HashSet<_>>();
+        xs.difference(&ys).map(|&&c| c).collect::<MinHeap<char>>()
+    };
+
+    log::debug!("Edges:");
+    for (k,v) in &edges {
+        log::debug!("{}: {:?}", k, v);
+    }
+    log::debug!("In-Degree: {:?}", indegrees);
+    log::debug!("Initial Queue: {:?}", queue);
+
+    // Workers pull instructions off the queue, and finish them in 
+    // the order: (chronological, lexographical)
+    //
+    // Tuples order by their first component, then their second - so time is
+    // higher priority than lexographical sort.
+    let mut workers: MinHeap<(u32, char)> = MinHeap::new();
+    // As instructions are finished, they get plopped on the output queue.
+    let mut out = Vec::<char>::new();
+    // The assembly is finished when the last worker is done.
+    let mut time_taken = 0;
+
+    while !(queue.is_empty() && workers.is_empty()) {
+        // Fill up as many workers as possible from items off the queue
+        while workers.len() < flags.nworkers && !queue.is_empty() {
+            let step = queue.pop().unwrap();
+            let deadline = time_taken + (step as u8 - b'A' + 1) as u32 + flags.time_offset;
+            workers.push((deadline, step))
+        }
+
+        // Step forward, simulating the next worker that would complete their instruction -
+        // along with any others done at the same moment, before anyone's handed more work.
+        let (deadline, _) = *workers.peek().unwrap();
+        time_taken = deadline;
+        while workers.peek().is_some_and(|&(d, _)| d == deadline) {
+            let (_, instr) = workers.pop().unwrap();
+            out.push(instr);
+
+            // Check all dependencies of the completed instruction,
+            // and queue up any that have had all their pre-instructions completed
+            // (signalled by having zero in-degrees).
+            if let Some(mut children) = edges.remove(&instr) {
+                // Where `children` is a lexographically ascending-sorted MinHeap.
+                while let Some(child) = children.pop() {
+                    let d = indegrees.get_mut(&child).unwrap();
+                    *d -= 1;
+                    if *d == 0 {
+                        queue.push(child);
+                    }
+                }
+            }
+        }
+        log::trace!("{:?}", &queue);
+    }
+
+    // Steps that are still waiting never got to begin: they're in a loop,
+    // each waiting on the next (or waiting on a step that is).
+    let mut stuck = indegrees.iter().filter(|(_, &d)| d > 0).map(|(&step, _)| step).collect::<Vec<_>>();
+    if !stuck.is_empty() {
+        stuck.sort_unstable();
+        return Err(NoAnswer::new(format!("steps {} wait on each other, so never begin", stuck.iter().collect::<String>())));
+    }
+
+    let order_of_steps = out.iter().collect::<String>();
+    Ok(Output{ order_of_steps, time_taken })
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Vec<(char, char)>;
+    type Config = Flags;
+    type Answer1 = String;
+    type Answer2 = u32;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Vec<(char, char)>, ParseError> { parse(puzzle) }
+    fn part1(digraphs: &Vec<(char, char)>, _: &Flags) -> Answer<String> { part1(digraphs) }
+    fn part2(digraphs: &Vec<(char, char)>, flags: &Flags) -> Answer<u32> { part2(digraphs, flags) }
+}
+
+impl Generate for Day07 {
+    const SIZE: usize = 26;
+
+    // Instructions for `size` steps (there are only 26 letters to name them
+    // with), each depending on at least one step from earlier on.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut steps = (b'A'..=b'Z').map(|b| b as char).collect::<Vec<_>>();
+        rng.shuffle(&mut steps);
+        steps.truncate(size.clamp(2, 26));
+
+        let mut digraphs = Vec::new();
+        for (idx, &after) in steps.iter().enumerate().skip(1) {
+            let first = rng.below(idx);
+            for (n, &before) in steps[..idx].iter().enumerate() {
+                if n == first || rng.chance(0.3) {
+                    digraphs.push((before, after));
+                }
+            }
+        }
+        rng.shuffle(&mut digraphs);
+        digraphs.iter()
+            .map(|(before, after)| format!("Step {} must be finished before step {} can begin.\n", before, after))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example is worked by 2 workers, with steps taking 1-26 seconds.
+    aoc_common::examples!(Day07, Flags{ nworkers: 2, time_offset: 0 });
+    aoc_common::generated!(Day07, 8);
+
+    #[test]
+    fn bad_steps() {
+        assert_eq!(parse("Step C must be finished before step a can begin."),
+                   Err(ParseError::BadStep { pos: Pos::new(1, 37), found: String::from("a") }));
+        assert_eq!(parse("Step C must be finished before step A can begin\nStep"),
+                   Err(ParseError::Expected { pos: Pos::new(1, 38), expected: " can begin.",
+                                              found: String::from(" can begin") }));
+        assert_eq!(parse("Step C must be done before step A can begin.").unwrap_err().pos(),
+                   Pos::new(1, 7));
+    }
+
+    // The puzzle's way of working through the steps: a second at a time,
+    // handing out whatever steps are ready, in order, to whoever's free.
+    fn reference(digraphs: &[(char, char)], flags: &Flags) -> Output {
+        let mut steps = digraphs.iter().flat_map(|&(v, w)| vec![v, w]).collect::<Vec<_>>();
+        steps.sort_unstable();
+        steps.dedup();
+
+        let (mut done, mut working, mut time_taken) = (String::new(), Vec::<(char, u32)>::new(), 0);
+        loop {
+            for &step in &steps {
+                let ready = digraphs.iter().filter(|&&(_, w)| w == step).all(|&(v, _)| done.contains(v));
+                if ready && working.len() < flags.nworkers && !done.contains(step)
+                         && working.iter().all(|&(s, _)| s != step) {
+                    working.push((step, (step as u8 - b'A' + 1) as u32 + flags.time_offset));
+                }
+            }
+            if working.is_empty() {
+                return Output{ order_of_steps: done, time_taken };
+            }
+            time_taken += 1;
+            for (_, left) in &mut working {
+                *left -= 1;
+            }
+            let mut finished = working.iter().filter(|&&(_, left)| left == 0).map(|&(s, _)| s).collect::<Vec<_>>();
+            finished.sort_unstable();
+            done.extend(finished);
+            working.retain(|&(_, left)| left > 0);
+        }
+    }
+
+    #[test]
+    fn against_reference() {
+        for (seed, digraphs) in aoc_common::generate::inputs::<Day07>(200, 12) {
+            let expect = reference(&digraphs, &Flags{ nworkers: 1, time_offset: 0 });
+            assert_eq!(part1(&digraphs), Ok(expect.order_of_steps), "seed {}", seed);
+            for flags in [Flags{ nworkers: 2, time_offset: 0 }, Flags{ nworkers: 3, time_offset: 5 }, Flags::default()].iter() {
+                let (fast, slow) = (assemble_sleigh(&digraphs, flags).unwrap(), reference(&digraphs, flags));
+                assert_eq!((fast.order_of_steps, fast.time_taken), (slow.order_of_steps, slow.time_taken),
+                           "seed {}, {} workers", seed, flags.nworkers);
+            }
+        }
+    }
+
+    #[test]
+    fn simultaneous_finishes() {
+        // Two steps finish together here, and the second frees up a step
+        // that has to go before the one the first frees up.
+        let digraphs = parse(&aoc_common::generate::generate::<Day07>(637, 12)).unwrap();
+        let flags = Flags{ nworkers: 2, time_offset: 0 };
+        assert_eq!(part2(&digraphs, &flags), Ok(reference(&digraphs, &flags).time_taken));
+    }
+
+    #[test]
+    fn loops() {
+        let digraphs = parse("Step A must be finished before step B can begin.\n\
+                              Step B must be finished before step C can begin.\n\
+                              Step C must be finished before step B can begin.\n\
+                              Step C must be finished before step D can begin.").unwrap();
+        assert_eq!(part1(&digraphs), Err(NoAnswer::new("steps BCD wait on each other, so never begin")));
+        assert!(part2(&parse("Step A must be finished before step A can begin.").unwrap(), &Flags::default()).is_err());
+        assert_eq!(part2(&digraphs, &Flags{ nworkers: 0, time_offset: 0 }),
+                   Err(NoAnswer::new("there are no workers to do the steps")));
+    }
+}
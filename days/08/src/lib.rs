@@ -0,0 +1,263 @@
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::{Cursor, Expected};
+
+// The license's nodes, each one after all of its children (so the root is
+// last). They're kept in a list rather than nested, so a license nested
+// thousands deep can't overflow the stack reading, solving, or dropping it.
+#[derive(Debug)]
+pub struct Tree {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug)]
+pub struct Node {
+    // Where the children are in the tree's nodes.
+    children: Vec<usize>,
+    data: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // The license is nothing but numbers.
+    BadNumber { pos: Pos, found: String },
+    // A node promised more children or metadata than there are numbers left.
+    Truncated { pos: Pos },
+    // Everything after the root node is left over.
+    Trailing { pos: Pos, found: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadNumber { pos, found } =>
+                write!(f, "{}: expected a number, found `{}`", pos, found),
+            ParseError::Truncated { pos } =>
+                write!(f, "{}: expected another number, found the end of the license", pos),
+            ParseError::Trailing { pos, found } =>
+                write!(f, "{}: expected the end of the license, found `{}`", pos, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::BadNumber { pos, .. }
+            | ParseError::Truncated { pos }
+            | ParseError::Trailing { pos, .. } => *pos,
+        }
+    }
+}
+
+// Every number in the license, and where it was found.
+struct Numbers<'a> {
+    puzzle: &'a str,
+    cursor: Cursor<'a>,
+}
+
+impl<'a> Numbers<'a> {
+    fn next(&mut self) -> Result<u32, ParseError> {
+        self.cursor.skip_while(char::is_whitespace);
+        if self.cursor.is_empty() {
+            return Err(ParseError::Truncated { pos: Pos::end_of(self.puzzle) });
+        }
+        self.cursor.take_while(|c| !c.is_whitespace()).all(Expected::Number, Cursor::int)
+            .map_err(|fail| ParseError::BadNumber { pos: fail.pos, found: fail.found.to_string() })
+    }
+}
+
+// Build the tree structure.
+// The input numbers are described in the puzzle instructions
+// (two fields for header, then variable amount of stuff).
+fn read_tree(nums: &mut Numbers) -> Result<Tree, ParseError> {
+    let mut nodes = Vec::new();
+    // The nodes part way through being read: how many children each has
+    // still to come, how much metadata after them, and the children so far.
+    let mut open = vec![(nums.next()?, nums.next()?, Vec::new())];
+    while let Some(node) = open.last_mut() {
+        if node.0 > 0 {
+            node.0 -= 1;
+            open.push((nums.next()?, nums.next()?, Vec::new()));
+            continue;
+        }
+        let (_, ndata, children) = open.pop().unwrap();
+        nodes.push(Node {
+            children,
+            data: (0..ndata).map(|_| nums.next()).collect::<Result<Vec<_>, _>>()?,
+        });
+        if let Some(parent) = open.last_mut() {
+            parent.2.push(nodes.len() - 1);
+        }
+    }
+    Ok(Tree{ nodes })
+}
+
+pub fn parse(puzzle: &str) -> Result<Tree, ParseError> {
+    let nums = &mut Numbers{ puzzle, cursor: Cursor::new(puzzle) };
+    let root = read_tree(nums)?;
+    nums.cursor.skip_while(char::is_whitespace);
+    if !nums.cursor.is_empty() {
+        let found = nums.cursor.take_while(|c| !c.is_whitespace());
+        return Err(ParseError::Trailing { pos: found.pos(), found: found.rest().to_string() });
+    }
+    Ok(root)
+}
+
+fn too_big() -> NoAnswer {
+    NoAnswer::new("the total is too big to count")
+}
+
+// "What is the sum of all metadata entries?"
+pub fn part1(tree: &Tree) -> Answer<u32> {
+    tree.nodes.iter().flat_map(|node| &node.data)
+        .try_fold(0u32, |sum, &n| sum.checked_add(n))
+        .ok_or_else(too_big)
+}
+
+// "What is the value of the root node?"
+pub fn part2(tree: &Tree) -> Answer<u32> {
+    // Every node's value, worked out once, children first: a child can be
+    // counted over and over, which makes the total exponentially big, nested
+    // deep enough. A value too big to count only matters if it's counted.
+    let mut values: Vec<Option<u32>> = Vec::with_capacity(tree.nodes.len());
+    for node in &tree.nodes {
+        // Only sum nodes without children, or the indexed-via-metadata children
+        let value = if node.children.is_empty() {
+            node.data.iter().try_fold(0u32, |sum, &n| sum.checked_add(n))
+        } else {
+            // Metadata 0 doesn't refer to any child, just like an out of range one.
+            node.data.iter()
+                .filter_map(|&data| (data as usize).checked_sub(1).and_then(|idx| node.children.get(idx)))
+                .try_fold(0u32, |sum, &child| sum.checked_add(values[child]?))
+        };
+        values.push(value);
+    }
+    values.last().copied().flatten().ok_or_else(too_big)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Tree;
+    type Config = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Tree, ParseError> { parse(puzzle) }
+    fn part1(tree: &Tree, _: &()) -> Answer<u32> { part1(tree) }
+    fn part2(tree: &Tree, _: &()) -> Answer<u32> { part2(tree) }
+}
+
+// Write out a node with `nodes - 1` descendants, in the license's format.
+fn generate_node(rng: &mut Rng, nodes: usize, out: &mut Vec<u32>) {
+    // Give each child at least itself, and hand out the rest at random.
+    let mut children = vec![1; if nodes > 1 { rng.between(1, (nodes as i64 - 1).min(8)) as usize } else { 0 }];
+    for _ in children.len()..nodes - 1 {
+        let child = rng.below(children.len());
+        children[child] += 1;
+    }
+    let metadata = rng.between(1, 11) as usize;
+    // Entries that are the indexes of children mostly hit one, but not always.
+    let most = if children.is_empty() { 9 } else { children.len() as i64 + 1 };
+
+    out.extend(&[children.len() as u32, metadata as u32]);
+    for child in children {
+        generate_node(rng, child, out);
+    }
+    out.extend((0..metadata).map(|_| rng.between(1, most) as u32));
+}
+
+impl Generate for Day08 {
+    const SIZE: usize = 2000;
+
+    // A license with `size` nodes, each with up to 8 children and 11
+    // metadata entries.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut license = Vec::new();
+        generate_node(rng, size.max(1), &mut license);
+        let numbers = license.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        numbers.join(" ") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::examples!(Day08);
+    aoc_common::generated!(Day08, 30);
+
+    #[test]
+    fn bad_license() {
+        let err = |puzzle| parse(puzzle).err().unwrap();
+        assert_eq!(err("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1"),
+                   ParseError::Truncated { pos: Pos::new(1, 34) });
+        assert_eq!(err("0 1 5\n7"),
+                   ParseError::Trailing { pos: Pos::new(2, 1), found: String::from("7") });
+        assert_eq!(err("1 1 0 x 3 4"),
+                   ParseError::BadNumber { pos: Pos::new(1, 7), found: String::from("x") });
+    }
+
+    #[test]
+    fn too_big() {
+        assert!(part1(&parse("0 2 4294967295 1").unwrap()).is_err());
+        // Every node counts its one child four times over, forty deep.
+        let license = "1 4 ".repeat(40) + "0 1 1" + &" 1 1 1 1".repeat(40);
+        assert_eq!(part1(&parse(&license).unwrap()), Ok(161));
+        assert!(part2(&parse(&license).unwrap()).is_err());
+        let license = "1 4 ".repeat(15) + "0 1 1" + &" 1 1 1 1".repeat(15);
+        assert_eq!(part2(&parse(&license).unwrap()), Ok(1 << 30));
+        // Too big to count, but never counted.
+        assert_eq!(part2(&parse("1 1 0 2 4294967295 1 2").unwrap()), Ok(0));
+    }
+
+    #[test]
+    fn deep() {
+        // Nested far deeper than there's stack for, one frame per node.
+        let license = "1 1 ".repeat(100_000) + "0 1 1" + &" 1".repeat(100_000);
+        let tree = parse(&license).unwrap();
+        assert_eq!((part1(&tree), part2(&tree)), (Ok(100_001), Ok(1)));
+    }
+
+    // Read a node straight off the numbers, without building a tree:
+    // returns its metadata sum, its value, and the numbers after it.
+    fn read(numbers: &[u32]) -> (u32, u32, &[u32]) {
+        let (children, entries) = (numbers[0] as usize, numbers[1] as usize);
+        let (mut sum, mut values, mut rest) = (0, Vec::new(), &numbers[2..]);
+        for _ in 0..children {
+            let (child_sum, value, after) = read(rest);
+            sum += child_sum;
+            values.push(value);
+            rest = after;
+        }
+        let data = &rest[..entries];
+        let value = if children == 0 {
+            data.iter().sum()
+        } else {
+            data.iter().filter(|&&n| n >= 1 && n as usize <= children).map(|&n| values[n as usize - 1]).sum()
+        };
+        (sum + data.iter().sum::<u32>(), value, &rest[entries..])
+    }
+
+    #[test]
+    fn against_reference() {
+        for seed in 0..50 {
+            let license = aoc_common::generate::generate::<Day08>(seed, 40);
+            let numbers = license.split_whitespace().map(|n| n.parse().unwrap()).collect::<Vec<u32>>();
+            let (sum, value, rest) = read(&numbers);
+            assert!(rest.is_empty(), "seed {}", seed);
+
+            let tree = parse(&license).unwrap();
+            assert_eq!((part1(&tree), part2(&tree)), (Ok(sum), Ok(value)), "seed {}", seed);
+        }
+    }
+}
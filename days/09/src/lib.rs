@@ -0,0 +1,251 @@
+/* Elves are dumb */
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::{Cursor, Expected, Fail};
+
+type Marble = u32;
+
+/* VecDeque is fantastic when items are placed on either end of the queue.
+   BUT NOTE: Can't use standard rotate_* methods on a VecDeque. */
+type Game = VecDeque<u32>;
+
+fn rotate_clockwise(game: &mut Game) {
+    let rot = game.pop_front().unwrap();
+    game.push_back(rot);
+}
+
+fn rotate_counter_clockwise(game: &mut Game) {
+    let rot = game.pop_back().unwrap();
+    game.push_front(rot);
+}
+
+// The rules of a game: how many elves are playing, and the last marble's value.
+pub struct Rules { players: u32, last_marble: u32 }
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // Some piece of "N players; last marble is worth M points".
+    Expected { pos: Pos, expected: &'static str, found: String },
+    BadNumber { pos: Pos, found: String },
+    // Somebody has to play the game.
+    NoPlayers { pos: Pos },
+    // The rules are only one line long.
+    Trailing { pos: Pos, found: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Expected { pos, expected, found } =>
+                write!(f, "{}: expected `{}`, found `{}`", pos, expected, found),
+            ParseError::BadNumber { pos, found } =>
+                write!(f, "{}: expected a number, found `{}`", pos, found),
+            ParseError::NoPlayers { pos } =>
+                write!(f, "{}: expected at least one player, found `0`", pos),
+            ParseError::Trailing { pos, found } =>
+                write!(f, "{}: expected the end of the input, found `{}`", pos, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::Expected { pos, .. }
+            | ParseError::BadNumber { pos, .. }
+            | ParseError::NoPlayers { pos }
+            | ParseError::Trailing { pos, .. } => *pos,
+        }
+    }
+}
+
+impl From<Fail<'_>> for ParseError {
+    fn from(fail: Fail) -> ParseError {
+        let (pos, found) = (fail.pos, fail.found.to_string());
+        match fail.expected {
+            Expected::Literal(expected) => ParseError::Expected { pos, expected, found },
+            Expected::Thing(_) => ParseError::NoPlayers { pos },
+            Expected::End => ParseError::Trailing { pos, found },
+            Expected::Number | Expected::InRange => ParseError::BadNumber { pos, found },
+        }
+    }
+}
+
+// Sample: "10 players; last marble is worth 1618 points"
+pub fn parse(puzzle: &str) -> Result<Rules, ParseError> {
+    let mut c = Cursor::new(puzzle);
+    c.skip_while(char::is_whitespace);
+    let players = c.check(Expected::Thing("at least one player"), Cursor::int, |&n| n > 0)?;
+    c.literal(" players; last marble is worth ")?;
+    let last_marble = c.int()?;
+    c.literal(" points")?;
+    c.skip_while(char::is_whitespace);
+    c.end()?;
+    Ok(Rules{ players, last_marble })
+}
+
+// Every marble that's played stays in the circle (well, most of them), so
+// much past this and there isn't the memory to play - or the time.
+const MAX_MARBLES: u32 = 100_000_000;
+
+fn play(rules: &Rules, embiggened: bool) -> Answer<u32> {
+    let factor = if embiggened {100} else {1};
+    let last_val = rules.last_marble.checked_mul(factor).filter(|&n| n <= MAX_MARBLES).ok_or_else(||
+        NoAnswer::new(format!("{} marbles are too many to play", rules.last_marble as u64 * factor as u64)))?;
+    let players = rules.players;
+    log::info!("players: {}, last_val: {}", players, last_val);
+
+    // Only the players who get a marble can score, however many more there are.
+    let mut scores = vec![0u32; players.min(last_val + 2) as usize];
+    let mut current: Marble = 0;
+
+    // Memory space caps out at the last marble placed,
+    // plus one initial, and subtracting all the marbles removed & not placed every 23.
+    let game = &mut VecDeque::with_capacity(
+        last_val as usize+1 - (last_val as usize / 23)*2
+    );
+    game.push_back(current);
+
+    // It's your old pal magic constants!
+    while current < last_val {
+        current += 1;
+        if !current.is_multiple_of(23) {
+            rotate_clockwise(game);
+            game.push_back(current);
+        } else {
+            // *something different*
+            for _ in 0..7 {
+                rotate_counter_clockwise(game);
+            }
+
+            let mut score = current;
+            score += game.pop_back().unwrap();
+
+            let idx = ((current+1) % players) as usize;
+            scores[idx] = scores[idx].checked_add(score)
+                .ok_or_else(|| NoAnswer::new("the winning score is too big to count"))?;
+
+            rotate_clockwise(game);
+        }
+    }
+    Ok(*scores.iter().max().unwrap())
+}
+
+// "What is the winning Elf's score?"
+pub fn part1(rules: &Rules) -> Answer<u32> {
+    play(rules, false)
+}
+
+// "What would the new winning Elf's score be if the number
+//  of the last marble were 100 times larger?"
+pub fn part2(rules: &Rules) -> Answer<u32> {
+    play(rules, true)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Rules;
+    type Config = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Rules, ParseError> { parse(puzzle) }
+    fn part1(rules: &Rules, _: &()) -> Answer<u32> { part1(rules) }
+    fn part2(rules: &Rules, _: &()) -> Answer<u32> { part2(rules) }
+}
+
+impl Generate for Day09 {
+    const SIZE: usize = 70_000;
+
+    // A game with a last marble worth `size` points, and enough players that
+    // the winner's score in the game 100 times as long still fits in a `u32`.
+    // The scores add up to about `(100 * size)^2 / 35`, shared out roughly
+    // evenly.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let last_marble = size.max(1) as i64;
+        let enough = (100 * last_marble).pow(2) / 35 / 3_000_000_000 + 1;
+        let players = rng.between(enough.max(9), enough.max(500));
+        format!("{} players; last marble is worth {} points\n", players, last_marble)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::generated!(Day09, 200);
+
+    #[test]
+    fn example_1() {
+        let tests = vec!(
+            ("10 players; last marble is worth 25 points", 32),
+            ("10 players; last marble is worth 1618 points", 8317),
+            ("13 players; last marble is worth 7999 points", 146373),
+            ("17 players; last marble is worth 1104 points", 2764),
+            ("21 players; last marble is worth 6111 points", 54718),
+            ("30 players; last marble is worth 5807 points", 37305),
+        );
+        for (test, expect) in tests {
+            assert_eq!(part1(&parse(test).unwrap()), Ok(expect));
+        }
+    }
+
+    #[test]
+    fn bad_rules() {
+        let err = |puzzle| parse(puzzle).err().unwrap();
+        assert_eq!(err("0 players; last marble is worth 25 points"),
+                   ParseError::NoPlayers { pos: Pos::new(1, 1) });
+        assert_eq!(err("10 players; last marble is worth lots of points"),
+                   ParseError::BadNumber { pos: Pos::new(1, 34), found: String::from("lots") });
+        assert_eq!(err("10 players, last marble is worth 25 points").pos(), Pos::new(1, 3));
+        assert_eq!(err("10 players; last marble is worth 25 points\nand more"),
+                   ParseError::Trailing { pos: Pos::new(2, 1), found: String::from("and more") });
+    }
+
+    #[test]
+    fn huge_games() {
+        let rules = parse("4294967295 players; last marble is worth 25 points").unwrap();
+        assert_eq!(part1(&rules), Ok(32));
+        let rules = parse("10 players; last marble is worth 4294967295 points").unwrap();
+        assert!(part1(&rules).is_err() && part2(&rules).is_err());
+        let rules = parse("1 players; last marble is worth 100000 points").unwrap();
+        assert_eq!((part1(&rules).is_ok(), part2(&rules).is_err()), (true, true));
+    }
+
+    // The game as the puzzle tells it, marble by marble, in a plain list.
+    fn reference(players: usize, last_marble: u32) -> u32 {
+        let (mut circle, mut current, mut scores) = (vec![0], 0, vec![0; players]);
+        for marble in 1..=last_marble {
+            if marble % 23 == 0 {
+                current = (current + circle.len() - 7) % circle.len();
+                scores[(marble as usize - 1) % players] += marble + circle.remove(current);
+            } else {
+                current = (current + 1) % circle.len() + 1;
+                circle.insert(current, marble);
+            }
+        }
+        scores.into_iter().max().unwrap()
+    }
+
+    #[test]
+    fn against_reference() {
+        for &size in [1, 22, 23, 24, 30, 46].iter() {
+            for (seed, rules) in aoc_common::generate::inputs::<Day09>(5, size) {
+                let players = rules.players as usize;
+                assert_eq!(part1(&rules), Ok(reference(players, rules.last_marble)), "seed {}, size {}", seed, size);
+                assert_eq!(part2(&rules), Ok(reference(players, rules.last_marble * 100)), "seed {}, size {}", seed, size);
+            }
+        }
+    }
+}
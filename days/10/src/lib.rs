@@ -0,0 +1,286 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::grid::Grid;
+use aoc_common::parse::{self, Cursor, Expected, Fail};
+use aoc_common::point::Point;
+
+type Pair = Point<i32>;
+
+static STAR_ON:  char = '#';
+static STAR_OFF: char = ' ';
+
+#[derive(Clone, Copy)]
+pub struct Star {
+    pos: Pair, // x, y
+    vel: Pair, // velocity of x, & y
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // Some piece of `position=< 9,  1> velocity=< 0,  2>` around the numbers.
+    Expected { pos: Pos, expected: &'static str, found: String },
+    BadNumber { pos: Pos, found: String },
+    // Each star gets a line to itself.
+    Trailing { pos: Pos, found: String },
+    // No stars, no message.
+    NoStars,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Expected { pos, expected, found } =>
+                write!(f, "{}: expected `{}`, found `{}`", pos, expected, found),
+            ParseError::BadNumber { pos, found } =>
+                write!(f, "{}: expected a number, found `{}`", pos, found),
+            ParseError::Trailing { pos, found } =>
+                write!(f, "{}: expected the end of the line, found `{}`", pos, found),
+            ParseError::NoStars => write!(f, "{}: expected a star, found nothing", self.pos()),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::Expected { pos, .. }
+            | ParseError::BadNumber { pos, .. }
+            | ParseError::Trailing { pos, .. } => *pos,
+            ParseError::NoStars => Pos::new(1, 1),
+        }
+    }
+}
+
+impl From<Fail<'_>> for ParseError {
+    fn from(fail: Fail) -> ParseError {
+        let (pos, found) = (fail.pos, fail.found.to_string());
+        match fail.expected {
+            Expected::Literal(expected) => ParseError::Expected { pos, expected, found },
+            Expected::End => ParseError::Trailing { pos, found },
+            _ => ParseError::BadNumber { pos, found },
+        }
+    }
+}
+
+// Numbers are padded out with spaces, to line up in columns.
+fn pair<'a>(c: &mut Cursor<'a>) -> Result<Pair, Fail<'a>> {
+    let number = |c: &mut Cursor<'a>, after| {
+        c.skip_while(|c| c == ' ');
+        let n = c.int()?;
+        c.skip_while(|c| c == ' ');
+        c.literal(after)?;
+        Ok(n)
+    };
+    Ok(Point::new(number(c, ",")?, number(c, ">")?))
+}
+
+// Sample: position=< 9,  1> velocity=< 0,  2>
+pub fn parse(puzzle: &str) -> Result<Vec<Star>, ParseError> {
+    let stars = parse::records(puzzle, |line| -> Result<_, ParseError> {
+        line.literal("position=<")?;
+        let pos = pair(line)?;
+        line.literal(" velocity=<")?;
+        Ok(Star{ pos, vel: pair(line)? })
+    })?;
+    if stars.is_empty() {
+        return Err(ParseError::NoStars);
+    }
+    Ok(stars)
+}
+
+// Calculate the bbox for the star pattern, as the top-left & bottom-right corners.
+fn bbox(stars: &[Star]) -> (Pair, Pair) {
+    let first = stars[0].pos;
+    let (mut min, mut max) = (first, first);
+    for star in stars.iter().skip(1).map(|sp| sp.pos) {
+        min = Point::new(min.x.min(star.x), min.y.min(star.y));
+        max = Point::new(max.x.max(star.x), max.y.max(star.y));
+    }
+    (min, max)
+}
+
+// The message is a line of letters, not a picture of the whole sky: if the
+// stars are never closer together than this, they don't spell anything.
+const MAX_SKY: i64 = 1 << 20;
+
+fn record_the_stars(stars: &[Star]) -> Answer<String> {
+    let (min, max) = bbox(stars);
+    let (width, height) = (max.x as i64 - min.x as i64 + 1, max.y as i64 - min.y as i64 + 1);
+    if width * height > MAX_SKY {
+        return Err(NoAnswer::new(format!("the stars are never closer together than {}x{}", width, height)));
+    }
+
+    let mut sky = Grid::new(width as usize, height as usize, STAR_OFF);
+
+    // Translate points
+    for star in stars {
+        sky[((star.pos.x as i64 - min.x as i64) as usize, (star.pos.y as i64 - min.y as i64) as usize)] = STAR_ON;
+    }
+
+    Ok(sky.render(|&c| c))
+}
+
+// Where the stars are after `time` seconds - if that's still in the sky.
+fn after(stars: &[Star], time: u64) -> Option<Vec<Star>> {
+    let at = |pos: i32, vel: i32| i32::try_from(pos as i128 + vel as i128 * time as i128).ok();
+    stars.iter()
+        .map(|&Star{ pos, vel }| Some(Star{ pos: Point::new(at(pos.x, vel.x)?, at(pos.y, vel.y)?), vel }))
+        .collect()
+}
+
+// At first, I guessed at how long to run the simulation, and printed out
+// every frame after 10,500 or so. But printing out the bbox each step showed
+// the stars "converge" - the message is spelled out exactly when the stars
+// are packed in the tightest. So, run until the bbox stops shrinking.
+//
+// The top and bottom of the bbox are always the furthest out stars, each
+// moving at its own steady speed, so once it stops shrinking it never starts
+// again. That means there's no need to run the simulation a second at a time
+// (for billions of seconds, for stars that start far enough apart): keep
+// doubling the time until the bbox stops shrinking, then split the
+// difference back to the second that it did.
+//
+// Returns the time it took the stars to spell the message.
+fn align(stars: &[Star]) -> u64 {
+    if stars.is_empty() { return 0 }
+
+    let height = |time: u64| {
+        let ys = stars.iter().map(|sp| sp.pos.y as i128 + sp.vel.y as i128 * time as i128);
+        ys.clone().max().unwrap() - ys.min().unwrap()
+    };
+    let shrinking = |time| height(time + 1) < height(time);
+
+    let mut later = 1;
+    while shrinking(later) {
+        later *= 2;
+    }
+    let mut time = 0;
+    while time < later {
+        let mid = time + (later - time) / 2;
+        if shrinking(mid) { time = mid + 1 } else { later = mid }
+    }
+    time
+}
+
+// "What message will eventually appear in the sky?"
+pub fn part1(stars: &[Star]) -> Answer<String> {
+    let stars = after(stars, align(stars))
+        .ok_or_else(|| NoAnswer::new("the stars drift out of the sky before they spell anything"))?;
+    record_the_stars(&stars)
+}
+
+// "exactly how many seconds would they have needed to wait
+//  for that message to appear?"
+pub fn part2(stars: &[Star]) -> u64 {
+    align(stars)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Star>;
+    type Config = ();
+    type Answer1 = String;
+    type Answer2 = u64;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Vec<Star>, ParseError> { parse(puzzle) }
+    fn part1(stars: &Vec<Star>, _: &()) -> Answer<String> { part1(stars) }
+    fn part2(stars: &Vec<Star>, _: &()) -> Answer<u64> { Ok(part2(stars)) }
+}
+
+impl Generate for Day10 {
+    const SIZE: usize = 330;
+
+    // `size` stars that come together after 5,000 to 15,000 seconds, ten
+    // rows high. Rather than letters, the message is random dots - with a
+    // pair of stars on the top and bottom rows heading in and out at the
+    // fastest speed of all, so the sky is tightest at exactly that moment.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const HEIGHT: i32 = 10;
+        const SPEED: i32 = 5;
+        let (size, time) = (size.max(4), rng.between(5_000, 15_000) as i32);
+        let (width, corner) = (size as i32 / 4 + 10, Pair::new(rng.between(0, 200) as i32, rng.between(0, 200) as i32));
+
+        let anchors = [(0, SPEED), (0, -SPEED), (HEIGHT - 1, SPEED), (HEIGHT - 1, -SPEED)];
+        let mut stars = (0..size).map(|n| {
+            let (y, vy) = anchors.get(n).copied()
+                .unwrap_or_else(|| (rng.between(0, HEIGHT as i64 - 1) as i32, rng.between(-SPEED as i64, SPEED as i64) as i32));
+            let at = corner + Pair::new(rng.between(0, width as i64 - 1) as i32, y);
+            let vel = Pair::new(rng.between(-SPEED as i64, SPEED as i64) as i32, vy);
+            Star{ pos: at - vel * time, vel }
+        }).collect::<Vec<_>>();
+        rng.shuffle(&mut stars);
+
+        stars.iter()
+            .map(|Star{ pos, vel }| format!("position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>\n", pos.x, pos.y, vel.x, vel.y))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::examples!(Day10);
+    aoc_common::generated!(Day10, 20);
+
+    #[test]
+    fn bad_stars() {
+        let err = |puzzle| parse(puzzle).err().unwrap();
+        assert_eq!(err("position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1>"),
+                   ParseError::Expected { pos: Pos::new(2, 31), expected: ",", found: String::from(">") });
+        assert_eq!(err("position=< 9,  1> velocity=< 0, two>"),
+                   ParseError::BadNumber { pos: Pos::new(1, 33), found: String::from("two") });
+        assert_eq!(err("position=< 9,  1> speed=< 0,  2>").pos(), Pos::new(1, 18));
+        assert_eq!(err("\n"), ParseError::NoStars);
+    }
+
+    #[test]
+    fn far_apart() {
+        let stars = parse("position=<0, -2000000000> velocity=<0, 1>\nposition=<0, 2000000000> velocity=<0, 0>").unwrap();
+        assert_eq!((part2(&stars), part1(&stars).unwrap().trim_end()), (4_000_000_000, "#"));
+        let stars = parse("position=<0, -2000000000> velocity=<0, 2000000000>\nposition=<0, 2000000000> velocity=<0, 1999999999>").unwrap();
+        assert!(part1(&stars).is_err());
+        let stars = parse("position=<0, 0> velocity=<0, 2000000000>\nposition=<0, 0> velocity=<0, -2000000000>").unwrap();
+        assert_eq!(part2(&stars), 0);
+        let stars = parse("position=<-2000000000, 0> velocity=<0, 0>\nposition=<2000000000, 0> velocity=<0, 0>").unwrap();
+        assert!(part1(&stars).is_err());
+    }
+
+    #[test]
+    fn against_reference() {
+        for (seed, stars) in aoc_common::generate::inputs::<Day10>(10, 20) {
+            // Work out where every star is at every second the message could
+            // appear, and take the first where they're packed tightest.
+            let at = |t: i32| stars.iter().map(|s| s.pos + s.vel * t).collect::<Vec<_>>();
+            let height = |t| {
+                let ys = at(t).iter().map(|p| p.y).collect::<Vec<_>>();
+                ys.iter().max().unwrap() - ys.iter().min().unwrap()
+            };
+            let time = (0..=20_000).min_by_key(|&t| height(t)).unwrap();
+
+            let sky = at(time);
+            let (x0, y0) = (sky.iter().map(|p| p.x).min().unwrap(), sky.iter().map(|p| p.y).min().unwrap());
+            let (x1, y1) = (sky.iter().map(|p| p.x).max().unwrap(), sky.iter().map(|p| p.y).max().unwrap());
+            let mut message = String::new();
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    message.push(if sky.contains(&Point::new(x, y)) { STAR_ON } else { STAR_OFF });
+                }
+                message.push('\n');
+            }
+
+            assert_eq!(part2(&stars), time as u64, "seed {}", seed);
+            assert_eq!(part1(&stars).unwrap().trim_end(), message.trim_end(), "seed {}", seed);
+        }
+    }
+}
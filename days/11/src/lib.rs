@@ -0,0 +1,252 @@
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::grid::Grid;
+use aoc_common::parse::{Cursor, Expected};
+
+pub type Serial = u32;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // The whole input is just the grid's serial number.
+    BadSerial { pos: Pos, found: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadSerial { pos, found } =>
+                write!(f, "{}: expected a grid serial number, found `{}`", pos, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::BadSerial { pos, .. } => *pos,
+        }
+    }
+}
+
+pub fn parse(puzzle: &str) -> Result<Serial, ParseError> {
+    let mut c = Cursor::new(puzzle);
+    c.skip_while(char::is_whitespace);
+    let serial = c.take_while(|c| !c.is_whitespace());
+    c.skip_while(char::is_whitespace);
+    c.end().and_then(|_| serial.all(Expected::Number, Cursor::int))
+        .map_err(|fail| ParseError::BadSerial { pos: fail.pos, found: fail.found.to_string() })
+}
+
+fn calc_power(x: usize, y: usize, grid_serial: Serial) -> i32 {
+    // Roomy enough for any serial, times a rack ID.
+    let rack_id = x as u64 + 10;
+    let mut score: u64 = rack_id * y as u64;
+    score += grid_serial as u64;
+    score *= rack_id;
+    score /= 100;
+    score %= 10;
+    (score as i32) - 5
+}
+
+#[derive(Debug)]
+struct BestID {
+    x: usize,
+    y: usize,
+    size: usize,
+    power: i32,
+}
+
+fn find_largest_power_by_dim(grid: &Grid<i32>, size: usize) -> BestID {
+    let mut largest = BestID{x:0,y:0,size,power:i32::MIN};
+
+    // Naive solution, heavily nested loops that sum all elements of the 3x3 window
+    for y in 0..=grid.height()-size {
+        for x in 0..=grid.width()-size {
+
+            let mut power: i32 = 0;
+            for row in y..y+size {
+                power += grid.row(row)[x..x+size].iter().sum::<i32>();
+            }
+
+            if power > largest.power {
+                largest = BestID{x:x+1,y:y+1,size,power};
+            }
+
+        }
+    }
+    largest
+}
+
+// "What is the X,Y coordinate of the top-left fuel cell of the 3x3 square
+//  with the largest total power?"
+pub fn part1(grid_serial: Serial) -> (usize,usize) {
+    let grid = Grid::from_fn(300, 300, |x, y| calc_power(x+1, y+1, grid_serial));
+
+    let tres = find_largest_power_by_dim(&grid, 3);
+    (tres.x, tres.y)
+}
+
+// moving_sums produces the power cell grid with each cell containing the
+// sum of all previous (left & above) power levels combined.
+fn moving_sums(grid_serial: Serial) -> Grid<i32> {
+    let mut grid = Grid::new(301, 301, 0);
+    // Carefully looking at the problem description, I found that the top row
+    // and left-most column can be left as zeros, removing the need for manual
+    // bounds checking. Coordinates always range from 1 to 300, inclusive.
+    for y in 1..grid.height() {
+        for x in 1..grid.width() {
+            let power = calc_power(x, y, grid_serial);
+            grid[(x,y)] = power
+                - grid[(x-1,y-1)] + grid[(x,y-1)] + grid[(x-1,y)];
+        }
+    }
+    grid
+}
+
+// The same as `find_largest_power_by_dim`, but with the sums: only the
+// value information bound by the window size being examined is needed.
+fn find_largest_power_by_sums(grid: &Grid<i32>, size: usize) -> BestID {
+    let mut largest = BestID{x:0,y:0,size,power:i32::MIN};
+
+    for y in 0..grid.height()-size {
+        for x in 0..grid.width()-size {
+            let power = grid[(x,y)]
+                + grid[(x+size,y+size)] - grid[(x,y+size)] - grid[(x+size,y)];
+            if power > largest.power {
+                largest = BestID{x:x+1,y:y+1,size,power};
+            }
+        }
+    }
+    largest
+}
+
+// The biggest square `part2` bothers with.
+const MAX_SIZE: usize = 30;
+
+// "What is the X,Y,size identifier of the square with the largest total power?"
+pub fn part2(grid_serial: Serial) -> (usize,usize,usize) {
+    let grid = moving_sums(grid_serial);
+
+    // There's no reason to try beyond a certain size, as the best totals
+    // plateau in the 10s or 20s: for every serial from 1 to 9999, the best
+    // square is somewhere from 5x5 to 27x27.
+    let mut largest = BestID{x:0, y:0, size:0, power: i32::MIN};
+    for size in 1..=MAX_SIZE {
+        let best = find_largest_power_by_sums(&grid, size);
+        if best.power > largest.power {
+            largest = best;
+        }
+    }
+    (largest.x, largest.y, largest.size)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Serial;
+    type Config = ();
+    type Answer1 = String;
+    type Answer2 = String;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Serial, ParseError> { parse(puzzle) }
+    fn part1(grid_serial: &Serial, _: &()) -> Answer<String> {
+        let (x, y) = part1(*grid_serial);
+        Ok(format!("{},{}", x, y))
+    }
+    fn part2(grid_serial: &Serial, _: &()) -> Answer<String> {
+        let (x, y, size) = part2(*grid_serial);
+        Ok(format!("{},{},{}", x, y, size))
+    }
+}
+
+impl Generate for Day11 {
+    // The grid is always 300x300, whatever the size.
+    const SIZE: usize = 300;
+
+    fn generate(rng: &mut Rng, _size: usize) -> String {
+        format!("{}\n", rng.between(1, 9999))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::generated!(Day11, Day11::SIZE);
+
+    #[test]
+    fn example_calc_power() {
+        struct Test { x: usize, y: usize, grid_serial: Serial }
+        let tests = vec!(
+            (Test{ x:  3, y:  5, grid_serial:  8 },  4),
+            (Test{ x:122, y: 79, grid_serial: 57 }, -5),
+            (Test{ x:217, y:196, grid_serial: 39 },  0),
+            (Test{ x:101, y:153, grid_serial: 71 },  4),
+            (Test{ x:300, y:300, grid_serial: Serial::MAX }, -1),
+        );
+        for (test, expect) in tests {
+            assert_eq!(calc_power(test.x, test.y, test.grid_serial), expect);
+        }
+    }
+
+    #[test]
+    fn example_1() {
+        let tests = vec!(
+            (18, (33,45)),
+            (42, (21,61)),
+        );
+        for (grid_serial, expect) in tests {
+            assert_eq!(part1(grid_serial), expect);
+        }
+    }
+
+    #[test]
+    fn example_2() {
+        let tests = vec!(
+            (18,  (90,269,16)),
+            (42, (232,251,12)),
+        );
+        for (grid_serial, expect) in tests {
+            assert_eq!(part2(grid_serial), expect);
+        }
+    }
+
+    #[test]
+    fn bad_serial() {
+        assert_eq!(parse("18\n"), Ok(18));
+        assert_eq!(parse("\n  -42"),
+                   Err(ParseError::BadSerial { pos: Pos::new(2, 3), found: String::from("-42") }));
+    }
+
+    #[test]
+    fn against_reference() {
+        for (seed, grid_serial) in aoc_common::generate::inputs::<Day11>(3, Day11::SIZE) {
+            let grid = Grid::from_fn(300, 300, |x, y| calc_power(x+1, y+1, grid_serial));
+            let sums = moving_sums(grid_serial);
+            for &size in [1, 2, 3, 5, 299, 300].iter() {
+                let (slow, fast) = (find_largest_power_by_dim(&grid, size), find_largest_power_by_sums(&sums, size));
+                assert_eq!((slow.x, slow.y, slow.power), (fast.x, fast.y, fast.power), "seed {}, size {}", seed, size);
+            }
+
+            let slow = find_largest_power_by_dim(&grid, 3);
+            assert_eq!(part1(grid_serial), (slow.x, slow.y), "seed {}", seed);
+
+            // Nothing bigger than the sizes tried is any better.
+            if seed < 2 {
+                let (_, _, size) = part2(grid_serial);
+                let best = find_largest_power_by_sums(&sums, size).power;
+                for other in MAX_SIZE + 1..=300 {
+                    assert!(find_largest_power_by_sums(&sums, other).power < best, "seed {}, size {}", seed, other);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::{self, Cursor, Fail};
+
+type Planters = HashMap<i32, bool>;
+type Rules = HashMap<u8, bool>;
+type Bounds = (i32,i32);
+
+fn debug_print(plants: &Planters, gen_id: impl std::fmt::Display) {
+    if !log::log_enabled!(log::Level::Debug) { return }
+
+    let keys = &mut plants.keys().collect::<Vec<_>>();
+    keys.sort();
+
+    let s = keys.iter().map(|b| if plants[b] {'#'} else {'.'}).collect::<String>();
+    log::debug!("{}: {}", gen_id, s);
+}
+
+fn next_gen(planters: &mut Planters, rules: &Rules, bounds: Bounds) -> Bounds {
+    let mut new_bounds = bounds;
+
+    // Plant pattern is a 'bitset' of five bits, that gets shifted onto for each
+    // pot index. e.g. if the next pot has a plant, 00010 would become 00101.
+    let mut plant_pattern = 0;
+    for plant_pos in bounds.0 - 2..=bounds.1 + 2 {
+        let next_bit = *planters.get(&(plant_pos+2)).unwrap_or(&false);
+        plant_pattern = plant_pattern << 1 | (next_bit as u8);
+        plant_pattern &= 0x1F; // strip to 5 bits
+
+        log::trace!("plants: {:05b}", plant_pattern);
+        let is_plant = *rules.get(&plant_pattern).unwrap_or(&false);
+        if plant_pos < bounds.0 || plant_pos > bounds.1 {
+            if is_plant {
+                new_bounds = (
+                    std::cmp::min(new_bounds.0, plant_pos),
+                    std::cmp::max(new_bounds.1, plant_pos));
+                planters.insert(plant_pos, is_plant);
+            }
+        } else {
+            planters.insert(plant_pos, is_plant);
+        }
+    }
+    new_bounds
+}
+
+// The initial state of the pots, and the rules for how they grow.
+pub struct Garden { planters: Planters, rules: Rules }
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // The fixed text: `initial state: `, or the ` => ` in a rule.
+    Expected { pos: Pos, expected: &'static str, found: String },
+    // Pots either have a plant (`#`) or don't (`.`).
+    BadPot { pos: Pos, found: char },
+    // Rules look at a pot and the two pots either side of it.
+    BadPattern { pos: Pos, found: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Expected { pos, expected, found } =>
+                write!(f, "{}: expected {}, found `{}`", pos, expected, found),
+            ParseError::BadPot { pos, found } =>
+                write!(f, "{}: expected a pot (`#` or `.`), found `{}`", pos, found),
+            ParseError::BadPattern { pos, found } =>
+                write!(f, "{}: expected a pattern of five pots, found `{}`", pos, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::Expected { pos, .. }
+            | ParseError::BadPot { pos, .. }
+            | ParseError::BadPattern { pos, .. } => *pos,
+        }
+    }
+}
+
+// Read a run of pots, as true/false 'has a plant'
+fn pots<'a>(mut c: Cursor<'a>) -> Result<impl Iterator<Item=bool> + 'a, ParseError> {
+    let run = c.take_while(|c| c == '#' || c == '.');
+    c.end().map_err(|fail| ParseError::BadPot { pos: fail.pos, found: c.peek().unwrap_or('\n') })?;
+    Ok(run.rest().bytes().map(|b| b == b'#'))
+}
+
+fn expected(fail: Fail, expected: &'static str) -> ParseError {
+    ParseError::Expected { pos: fail.pos, expected, found: fail.found.to_string() }
+}
+
+pub fn parse(puzzle: &str) -> Result<Garden, ParseError> {
+    let mut lines = parse::lines(puzzle);
+
+    // Build a map of pot index -> true/false 'has a plant'
+    let mut line = lines.next().unwrap_or_else(|| Cursor::new(puzzle));
+    line.literal("initial state: ").map_err(|fail| expected(fail, "`initial state: `"))?;
+    let planters: Planters = (0..i32::MAX).zip(pots(line)?).collect();
+
+    // Build a map of rules transforming a 5-bit pattern
+    // as a u8 (acting as a bitset) -> true/false 'becomes a plant'
+    let mut rules = Rules::new();
+    for mut line in lines {
+        let pattern = line.take_while(|c| c != ' ');
+        line.literal(" => ").map_err(|fail| expected(fail, "` => `"))?;
+
+        let a = pots(pattern)?.fold(0, |n, plant| (n << 1) | (plant as u8));
+        if pattern.rest().len() != 5 {
+            return Err(ParseError::BadPattern { pos: pattern.pos(), found: pattern.rest().to_string() });
+        }
+        let mut b = pots(line)?;
+        match (b.next(), b.next()) {
+            (Some(plant), None) => rules.insert(a, plant),
+            _ => return Err(ParseError::Expected { pos: line.pos(), expected: "a single pot",
+                                                   found: line.rest().to_string() }),
+        };
+    }
+
+    Ok(Garden{ planters, rules })
+}
+
+// "After 20 generations, what is the sum of the numbers of all pots which
+//  contain a plant?"
+//
+// "After fifty billion (50000000000) generations, what is the sum of the
+//  numbers of all pots which contain a plant?"
+pub fn simulate(garden: &Garden, generations: u64) -> Answer<i64> {
+    Ok(grow(garden, generations)?.0)
+}
+
+// Real gardens settle down within a couple of hundred generations. One that
+// hasn't after this many isn't going to be simulated for fifty billion.
+const MAX_GENERATIONS: u64 = 1_000;
+
+// The score after some generations, and (if it did) the generation when the
+// growth stabilized.
+fn grow(garden: &Garden, generations: u64) -> Answer<(i64, Option<u64>)> {
+    let (mut planters, rules) = (garden.planters.clone(), &garden.rules);
+    if rules.get(&0) == Some(&true) {
+        return Err(NoAnswer::new("`..... => #` grows a plant in every empty pot, out to infinity"));
+    }
+
+    // Track the first & last planter box as they grow outward
+    let mut bounds: Bounds = (0, planters.len() as i32 - 1);
+    // Track which pots have plants each generation, looking for when they're
+    // the same as the generation before, only shifted along - at which point,
+    // the pot pattern just grows out as a gliders, forever. (The score's
+    // delta between generations matching isn't enough: that can happen by
+    // chance, while the plants are still changing.)
+    let plants = |planters: &Planters| {
+        let mut plants = planters.iter().filter(|(_, &plant)| plant).map(|(&idx, _)| idx as i64).collect::<Vec<_>>();
+        plants.sort_unstable();
+        plants
+    };
+    let mut last = plants(&planters);
+
+    for i in 0..generations.min(MAX_GENERATIONS) {
+        bounds = next_gen(&mut planters, rules, bounds);
+        debug_print(&planters, i + 1);
+
+        // Calculate score by adding the indexes of pots with a plant in them
+        let now = plants(&planters);
+        let score = now.iter().sum::<i64>();
+
+        // If the plants have all moved the same way, then a cycle has been reached.
+        //
+        // This trick became apparent when printing out the first 100 generations.
+        let shift = now.first().zip(last.first()).map_or(0, |(a, b)| a - b);
+        if now.len() == last.len() && now.iter().zip(&last).all(|(a, b)| a - b == shift) {
+            log::info!("Growth stabilized after gen<{}>", i);
+            // The final score just adds the score delta for the remaining 4.99 billion
+            // generations to the score so far.
+            let delta = shift * now.len() as i64;
+            return Ok((score + delta * (generations - i - 1) as i64, Some(i)));
+        }
+        last = now;
+    }
+    if generations > MAX_GENERATIONS {
+        return Err(NoAnswer::new(format!("the plants are still changing after {} generations", MAX_GENERATIONS)));
+    }
+    Ok((last.iter().sum(), None))
+}
+
+pub fn part1(garden: &Garden) -> Answer<i64> {
+    simulate(garden, 20)
+}
+
+pub fn part2(garden: &Garden) -> Answer<i64> {
+    simulate(garden, 50_000_000_000)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Garden;
+    type Config = ();
+    type Answer1 = i64;
+    type Answer2 = i64;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Garden, ParseError> { parse(puzzle) }
+    fn part1(garden: &Garden, _: &()) -> Answer<i64> { part1(garden) }
+    fn part2(garden: &Garden, _: &()) -> Answer<i64> { part2(garden) }
+}
+
+impl Generate for Day12 {
+    const SIZE: usize = 100;
+
+    // A row of `size` pots, and whether each of the 32 patterns of five pots
+    // grows a plant - about a third do, except `.....`, which never does, or
+    // there'd be plants springing up forever. Only rules that settle down
+    // within 150 generations (with plants still around) are kept, so part 2
+    // has an answer.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let pot = |plant| if plant { '#' } else { '.' };
+        loop {
+            let state = (0..size.max(1)).map(|_| pot(rng.chance(0.5))).collect::<String>();
+            let mut rules = (0..32u8).map(|pattern| {
+                let pots = (0..5).rev().map(|bit| pot(pattern >> bit & 1 == 1)).collect::<String>();
+                format!("{} => {}\n", pots, pot(pattern != 0 && rng.chance(0.3)))
+            }).collect::<Vec<_>>();
+            rng.shuffle(&mut rules);
+
+            let puzzle = format!("initial state: {}\n\n{}", state, rules.concat());
+            let garden = parse(&puzzle).expect("generated an unreadable garden");
+            if let Ok((score, Some(_))) = grow(&garden, 150) {
+                if score != 0 {
+                    return puzzle;
+                }
+            }
+        }
+    }
+}
+
+aoc_common::examples!(Day12);
+aoc_common::generated!(Day12, 20);
+
+#[test]
+fn bad_garden() {
+    let err = |puzzle| parse(puzzle).err().unwrap();
+    assert_eq!(err("initial state #..#\n\n...## => #").pos(), Pos::new(1, 1));
+    assert_eq!(err("initial state: #..x\n\n...## => #"),
+               ParseError::BadPot { pos: Pos::new(1, 19), found: 'x' });
+    assert_eq!(err("initial state: #..#\n\n...## -> #"),
+               ParseError::Expected { pos: Pos::new(3, 6), expected: "` => `", found: String::from(" -> #") });
+    assert_eq!(err("initial state: #..#\n\n..## => #"),
+               ParseError::BadPattern { pos: Pos::new(3, 1), found: String::from("..##") });
+}
+
+#[test]
+fn unsettled() {
+    // A pot grows a plant when just one of its neighbours has one: from a
+    // lone plant, that draws a Sierpinski triangle, which never settles down.
+    let rules = (0..32u8).filter(|pattern| pattern >> 3 & 1 != pattern >> 1 & 1).map(|pattern| {
+        let pots = (0..5).rev().map(|bit| if pattern >> bit & 1 == 1 { '#' } else { '.' }).collect::<String>();
+        format!("{} => #\n", pots)
+    }).collect::<String>();
+    let garden = parse(&format!("initial state: #\n\n{}", rules)).unwrap();
+    assert_eq!(part1(&garden), Ok(reference(&garden, 20)));
+    assert!(part2(&garden).is_err());
+    let garden = parse("initial state: #\n\n..... => #").unwrap();
+    assert!(part1(&garden).is_err());
+}
+
+// The pots as the puzzle has them, generation by generation, keeping just
+// the numbers of the pots with plants in them.
+#[cfg(test)]
+fn reference(garden: &Garden, generations: u64) -> i64 {
+    use std::collections::BTreeSet;
+
+    let mut plants = garden.planters.iter().filter(|(_, &plant)| plant).map(|(&pot, _)| pot as i64).collect::<BTreeSet<_>>();
+    for _ in 0..generations {
+        let (first, last) = match (plants.iter().next(), plants.iter().next_back()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => break,
+        };
+        plants = (first - 2..=last + 2).filter(|pot| {
+            let pattern = (pot - 2..=pot + 2).fold(0, |n, p| n << 1 | plants.contains(&p) as u8);
+            garden.rules.get(&pattern).copied().unwrap_or(false)
+        }).collect();
+    }
+    plants.iter().sum()
+}
+
+#[test]
+fn against_reference() {
+    for (seed, garden) in aoc_common::generate::inputs::<Day12>(15, 20) {
+        assert_eq!(part1(&garden), Ok(reference(&garden, 20)), "seed {}", seed);
+        for &generations in [150, 500].iter() {
+            assert_eq!(simulate(&garden, generations), Ok(reference(&garden, generations)), "seed {}, {} generations", seed, generations);
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::iter::Cycle;
+use std::slice;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::grid::{Grid, GridError};
+use aoc_common::point::{Dir, Point, Turn};
+
+// The mine: a grid of track tiles, and the carts riding around on them.
+pub struct Mine {
+    carts: Vec<Cart>,
+    map: Grid<Track>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // Something that's neither track nor a cart.
+    BadTile { pos: Pos, found: char },
+    // The map is a rectangle, so every line is as wide as the first.
+    Ragged { pos: Pos, expected: usize, found: usize },
+    // A mine with no carts never has a crash.
+    NoCarts,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadTile { pos, found } =>
+                write!(f, "{}: expected a track tile (`-|/\\+ `) or cart (`^>v<`), found `{}`", pos, found),
+            ParseError::Ragged { pos, expected, found } =>
+                write!(f, "{}: expected a line {} tiles wide, found {}", pos, expected, found),
+            ParseError::NoCarts => write!(f, "{}: expected a map with carts, found none", self.pos()),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::BadTile { pos, .. } | ParseError::Ragged { pos, .. } => *pos,
+            ParseError::NoCarts => Pos::new(1, 1),
+        }
+    }
+}
+
+// parse the puzzle input tiles into a 2d matrix of track tiles + list of mine carts
+pub fn parse(puzzle: &str) -> Result<Mine, ParseError> {
+    let mut carts = Vec::<Cart>::new();
+    let map = Grid::parse(puzzle, |tile, x, y| Ok(match tile {
+        '-' | '|' => Track::Rail,
+        ' '       => Track::Empty,
+        '\\'      => Track::CornerBkwd,
+        '/'       => Track::CornerFwd,
+        '+'       => Track::Intersection,
+        _         => {
+            // Assume any carts have a rail line under them
+            carts.push(Cart::new(
+                Loc::new(x, y),
+                match tile {
+                    '^' => Dir::North,
+                    '>' => Dir::East,
+                    'v' => Dir::South,
+                    '<' => Dir::West,
+                    _   => return Err(ParseError::BadTile { pos: Pos::new(y + 1, x + 1), found: tile }),
+                }));
+
+            Track::Rail
+        },
+    })).map_err(|e| match e {
+        GridError::Cell(e) => e,
+        GridError::Ragged { pos, expected, found } => ParseError::Ragged { pos, expected, found },
+    })?;
+
+    if carts.is_empty() {
+        return Err(ParseError::NoCarts);
+    }
+    Ok(Mine{ carts, map })
+}
+
+// Where a cart is on the map.
+pub type Loc = Point<usize>;
+
+const TURNS: [Turn; 3] = [Turn::Left, Turn::Straight, Turn::Right];
+
+// The Track / map is made up of these set pieces.
+enum Track { Rail, CornerBkwd, CornerFwd, Intersection, Empty, }
+
+// Mine Carts have a position, direction they're facing, and record of the next turn they'll make.
+#[derive(Clone)]
+struct Cart {
+    loc: Loc,
+    dir: Dir,
+    turns: Cycle<slice::Iter<'static, Turn>>,
+}
+
+impl Cart {
+    fn new(loc: Loc, dir: Dir) -> Cart {
+        Cart {loc, dir, turns: TURNS.iter().cycle(), }
+    }
+
+    fn tick(&mut self, map: &Grid<Track>) -> Result<(), NoAnswer> {
+        // Move foward, then turn if on a '/\' corner or '+' intersection
+        self.loc = self.loc.step(self.dir)
+            .filter(|loc| map.contains((loc.x, loc.y)))
+            .ok_or_else(|| NoAnswer::new(format!("a cart runs off the edge of the map from {}", self.loc)))?;
+        let env = &map[(self.loc.x, self.loc.y)];
+        match env {
+            Track::Rail => {},
+            Track::CornerFwd => { // forward slash '/'
+                self.dir = match self.dir {
+                    Dir::East  | Dir::West  => self.dir.left(),
+                    Dir::North | Dir::South => self.dir.right(),
+                }
+            },
+            Track::CornerBkwd => { // backward slash '\'
+                self.dir = match self.dir {
+                    Dir::East  | Dir::West  => self.dir.right(),
+                    Dir::North | Dir::South => self.dir.left(),
+                }
+            },
+            Track::Intersection => {
+                // Take the next 'memorized' series of turns
+                self.dir = self.dir.turn(*self.turns.next().expect("Next turn"));
+            },
+            Track::Empty => return Err(NoAnswer::new(format!("a cart goes off the rails at {}", self.loc))),
+        }
+        Ok(())
+    }
+}
+
+// Carts that haven't crashed after this many moves (between all of them)
+// aren't going to: the real ones are down to the last cart in about fifty
+// thousand.
+const MAX_MOVES: usize = 1_000_000;
+
+fn safe() -> NoAnswer {
+    NoAnswer::new(format!("the mine carts are still running after {} moves", MAX_MOVES))
+}
+
+// "...you'd like to know the location of the first crash."
+pub fn part1(mine: &Mine) -> Answer<Loc> {
+    let (mut carts, map) = (mine.carts.clone(), &mine.map);
+
+    let mut cart_positions: HashSet<Loc> = carts.iter().map(|cart| cart.loc).collect();
+
+    for _tick in 0..MAX_MOVES / carts.len() {
+        carts.sort_by_key(|c| (c.loc.y, c.loc.x));
+
+        for cart in carts.iter_mut() {
+            cart_positions.remove(&cart.loc);
+
+            cart.tick(map)?;
+
+            let spot_taken = !cart_positions.insert(cart.loc);
+            if spot_taken {
+                log::info!("Crash detected! @ {}", cart.loc);
+                return Ok(cart.loc);
+            }
+        }
+    }
+    Err(safe())
+}
+
+// "What is the location of the last cart at the end of the first tick where
+//  it is the only cart left?"
+pub fn part2(mine: &Mine) -> Answer<Loc> {
+    let (mut carts, map) = (mine.carts.clone(), &mine.map);
+
+    let mut cart_positions: HashSet<Loc> = carts.iter().map(|cart| cart.loc).collect();
+
+    let mut moves = 0;
+    while moves < MAX_MOVES {
+        carts.sort_by_key(|c| (c.loc.y, c.loc.x));
+        moves += carts.len();
+
+        // Crashed carts come off the tracks straight away: another cart can
+        // roll through the same spot later in the tick, and carry on.
+        let mut crashed = vec![false; carts.len()];
+        for i in 0..carts.len() {
+            if crashed[i] { continue; }
+            cart_positions.remove(&carts[i].loc);
+
+            carts[i].tick(map)?;
+
+            let loc = carts[i].loc;
+            let spot_taken = !cart_positions.insert(loc);
+            if spot_taken {
+                log::info!("Crash detected! @ {}", loc);
+                cart_positions.remove(&loc);
+                let other = (0..carts.len()).find(|&j| j != i && !crashed[j] && carts[j].loc == loc);
+                crashed[i] = true;
+                crashed[other.expect("Crashed into nothing!")] = true;
+            }
+        }
+
+        // Prune crashed carts
+        if crashed.contains(&true) {
+            let mut crashed = crashed.into_iter();
+            carts.retain(|_| !crashed.next().unwrap());
+        }
+        match carts.len() {
+            0 => return Err(NoAnswer::new("every cart crashes, leaving none")),
+            1 => return Ok(carts[0].loc),
+            _ => {},
+        }
+    }
+    Err(safe())
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Mine;
+    type Config = ();
+    type Answer1 = Loc;
+    type Answer2 = Loc;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Mine, ParseError> { parse(puzzle) }
+    fn part1(mine: &Mine, _: &()) -> Answer<Loc> { part1(mine) }
+    fn part2(mine: &Mine, _: &()) -> Answer<Loc> { part2(mine) }
+}
+
+// The tiles around a `w` by `h` loop of track, clockwise from its top left
+// corner, and which way is clockwise on each.
+fn loop_tiles(w: usize, h: usize) -> Vec<(Loc, Dir)> {
+    let top = (0..w - 1).map(|x| (Loc::new(x, 0), Dir::East));
+    let right = (0..h - 1).map(|y| (Loc::new(w - 1, y), Dir::South));
+    let bottom = (1..w).rev().map(|x| (Loc::new(x, h - 1), Dir::West));
+    let left = (1..h).rev().map(|y| (Loc::new(0, y), Dir::North));
+    top.chain(right).chain(bottom).chain(left).collect()
+}
+
+// Where `count` carts can go on a `w` by `h` loop, at least 3 tiles apart,
+// and none of them on a corner. There might not be room for them all.
+fn cart_spots(rng: &mut Rng, w: usize, h: usize, count: usize) -> Option<Vec<usize>> {
+    let len = 2 * (w - 1) + 2 * (h - 1);
+    let corners = [0, w - 1, w + h - 2, 2 * w + h - 3];
+    let mut spots = (0..len).filter(|n| !corners.contains(n)).collect::<Vec<_>>();
+    rng.shuffle(&mut spots);
+
+    let mut taken = Vec::new();
+    for spot in spots {
+        if taken.iter().all(|&t: &usize| { let d = t.max(spot) - t.min(spot); d.min(len - d) >= 3 }) {
+            taken.push(spot);
+        }
+    }
+    if taken.len() < count { None } else { taken.truncate(count); Some(taken) }
+}
+
+impl Generate for Day13 {
+    const SIZE: usize = 17;
+
+    // `size` carts (or one more, to make it odd) on separate loops of track.
+    // On each loop, as many carts go clockwise as anticlockwise, bar one
+    // loop with an extra clockwise cart, so crashes always take out a pair
+    // heading opposite ways until only that cart's left. Carts start at least
+    // 3 tiles apart, so those going the same way never run into each other
+    // (or into a crash).
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const WIDTH: usize = 150;
+        let pairs = size.max(3) / 2;
+        let loops = rng.between(1, pairs as i64) as usize;
+        let mut loop_pairs = vec![0; loops];
+        for pair in 0..pairs {
+            loop_pairs[if pair < loops { pair } else { rng.below(loops) }] += 1;
+        }
+        let lone = rng.below(loops);
+
+        let mut rows: Vec<Vec<char>> = Vec::new();
+        let (mut at, mut row_height) = (Loc::new(0, 0), 0);
+        for (n, &pairs) in loop_pairs.iter().enumerate() {
+            let (clockwise, count) = (pairs + (n == lone) as usize, 2 * pairs + (n == lone) as usize);
+            let (mut w, mut h) = (rng.between(3, 30) as usize, rng.between(3, 20) as usize);
+            let spots = loop {
+                match cart_spots(rng, w, h, count) {
+                    Some(spots) => break spots,
+                    None => { w += 2; h += 1; },
+                }
+            };
+
+            if at.x + w > WIDTH && at.x > 0 {
+                at = Loc::new(0, at.y + row_height + 1);
+                row_height = 0;
+            }
+            row_height = row_height.max(h);
+            rows.resize(rows.len().max(at.y + h), Vec::new());
+
+            let tiles = loop_tiles(w, h);
+            let corners = [(Loc::new(0, 0), '/'), (Loc::new(w - 1, 0), '\\'),
+                           (Loc::new(w - 1, h - 1), '/'), (Loc::new(0, h - 1), '\\')];
+            for (idx, &(loc, dir)) in tiles.iter().enumerate() {
+                let tile = match spots.iter().position(|&spot| spot == idx) {
+                    Some(cart) => {
+                        let dir = if cart < clockwise { dir } else { dir.reverse() };
+                        match dir { Dir::North => '^', Dir::East => '>', Dir::South => 'v', Dir::West => '<' }
+                    },
+                    None => corners.iter().find(|c| c.0 == loc).map_or(
+                        if dir == Dir::East || dir == Dir::West { '-' } else { '|' }, |c| c.1),
+                };
+                let row = &mut rows[at.y + loc.y];
+                row.resize(row.len().max(at.x + loc.x + 1), ' ');
+                row[at.x + loc.x] = tile;
+            }
+            at.x += w + 1;
+        }
+
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        rows.iter().map(|row| format!("{:<width$}\n", row.iter().collect::<String>(), width = width)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::examples!(Day13);
+    aoc_common::generated!(Day13, 9);
+
+    #[test]
+    fn bad_map() {
+        let err = |puzzle| parse(puzzle).err().unwrap();
+        assert_eq!(err("/->-\\\n|   |\n\\-x-/"), ParseError::BadTile { pos: Pos::new(3, 3), found: 'x' });
+        assert_eq!(err("/->-\\\n|  |\n\\---/"),
+                   ParseError::Ragged { pos: Pos::new(2, 5), expected: 5, found: 4 });
+        assert_eq!(err("/---\\\n\\---/"), ParseError::NoCarts);
+    }
+
+    // Run the carts with no bookkeeping beyond whether each is still going,
+    // looking through all of them for a crash after every move. Returns
+    // every crash, in order, and where the last cart was left, if one was.
+    fn reference(mine: &Mine) -> (Vec<Loc>, Option<Loc>) {
+        let mut carts = mine.carts.iter().map(|cart| (cart.clone(), true)).collect::<Vec<_>>();
+        let mut crashes = Vec::new();
+        while carts.iter().filter(|(_, running)| *running).count() > 1 {
+            carts.sort_by_key(|(c, _)| (c.loc.y, c.loc.x));
+            for i in 0..carts.len() {
+                if !carts[i].1 {
+                    continue;
+                }
+                carts[i].0.tick(&mine.map).unwrap();
+                let loc = carts[i].0.loc;
+                if let Some(j) = (0..carts.len()).find(|&j| j != i && carts[j].1 && carts[j].0.loc == loc) {
+                    carts[i].1 = false;
+                    carts[j].1 = false;
+                    crashes.push(loc);
+                }
+            }
+        }
+        (crashes, carts.iter().find(|(_, running)| *running).map(|(c, _)| c.loc))
+    }
+
+    #[test]
+    fn against_reference() {
+        for (seed, mine) in aoc_common::generate::inputs::<Day13>(40, 9) {
+            let (crashes, last) = reference(&mine);
+            assert_eq!(part1(&mine).ok(), crashes.first().copied(), "seed {}", seed);
+            assert_eq!(part2(&mine).ok(), last, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn through_a_crash() {
+        // The two carts on top crash in the middle, and are gone before the
+        // one underneath moves up into the same spot.
+        let mine = parse("->+<-\n  ^  \n").unwrap();
+        assert_eq!(reference(&mine), (vec![Loc::new(2, 0)], Some(Loc::new(2, 0))));
+        assert_eq!(part1(&mine), Ok(Loc::new(2, 0)));
+        assert_eq!(part2(&mine), Ok(Loc::new(2, 0)));
+    }
+
+    #[test]
+    fn no_last_cart() {
+        // Off the rails, and off the edge of the map.
+        assert!(part1(&parse("-> \n").unwrap()).is_err());
+        assert!(part2(&parse("<-\n").unwrap()).is_err());
+        // Every cart crashes.
+        let mine = parse("-><-\n").unwrap();
+        assert_eq!((part1(&mine), part2(&mine).is_err()), (Ok(Loc::new(2, 0)), true));
+        // No cart ever crashes, when there's only the one.
+        let mine = parse("/>\\\n\\-/\n").unwrap();
+        assert_eq!((part1(&mine).is_err(), part2(&mine)), (true, Ok(Loc::new(2, 0))));
+    }
+}
@@ -0,0 +1,241 @@
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Answer, NoAnswer, Solution};
+use aoc_common::diagnostic::{Diagnostic, Pos};
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::Cursor;
+
+// The puzzle input is a single number, read two ways: as a count of recipes
+// for part 1, and as a sequence of recipe scores for part 2.
+pub struct Scoreboard {
+    recipes: usize,
+    scores: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    BadDigit { pos: Pos, found: char },
+    // Too many recipes to ever fit on the scoreboard.
+    TooLarge { pos: Pos, found: String },
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadDigit { pos, found } =>
+                write!(f, "{}: expected a digit, found `{}`", pos, found),
+            ParseError::TooLarge { pos, found } =>
+                write!(f, "{}: expected a number of recipes up to {}, found `{}`", pos, usize::MAX, found),
+            ParseError::Empty => write!(f, "{}: expected a number, found nothing", self.pos()),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn pos(&self) -> Pos {
+        match self {
+            ParseError::BadDigit { pos, .. } | ParseError::TooLarge { pos, .. } => *pos,
+            ParseError::Empty => Pos::new(1, 1),
+        }
+    }
+}
+
+pub fn parse(puzzle: &str) -> Result<Scoreboard, ParseError> {
+    let mut c = Cursor::new(puzzle);
+    c.skip_while(char::is_whitespace);
+    let mut digits = c.take_while(|d| d.is_ascii_digit());
+    c.end().map_err(|fail| ParseError::BadDigit { pos: fail.pos, found: c.peek().unwrap_or('\n') })?;
+    if digits.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let input = digits.rest();
+    let recipes = digits.int()
+        .map_err(|fail| ParseError::TooLarge { pos: fail.pos, found: fail.found.to_string() })?;
+    Ok(Scoreboard{ recipes, scores: input.bytes().map(|b| b - b'0').collect() })
+}
+
+// Real puzzles are done in about twenty million recipes. The scoreboard
+// takes a byte for every recipe, so it's not let grow too far past that.
+const MAX_RECIPES: usize = 50_000_000;
+
+// "What are the scores of the ten recipes immediately after the number of
+//  recipes in your puzzle input?"
+pub fn part1(board: &Scoreboard) -> Answer<String> {
+    let end = board.recipes;
+    if end > MAX_RECIPES - 10 {
+        return Err(NoAnswer::new(format!("{} recipes won't fit on the scoreboard", end)));
+    }
+
+    let mut recipes = Vec::<u8>::with_capacity(end + 11);
+    recipes.extend([3, 7].iter());
+
+    // Track recipe indexes of the two elves
+    let mut elves: [usize; 2] = [0, 1];
+
+    loop {
+        let new_recipe: u8 = elves.iter().map(|&elf_pos| recipes[elf_pos]).sum();
+        // Add the digits from the 'recipe' sum to the list, highest digit first
+        if new_recipe >= 10 {
+            recipes.push(new_recipe / 10);
+        }
+        recipes.push(new_recipe % 10);
+
+        // Move elves forward by their position + recipe score + 1
+        for elf in elves.iter_mut() {
+            *elf = (*elf + (recipes[*elf] + 1) as usize) % recipes.len();
+        }
+
+        if recipes.len() > end + 10 {
+            return Ok(recipes[end..end+10].iter().map(|b| b.to_string()).collect::<String>())
+        }
+    }
+}
+
+
+// "How many recipes appear on the scoreboard to the left of the score sequence
+//  in your puzzle input?"
+pub fn part2(board: &Scoreboard) -> Answer<usize> {
+    let score_seq = &board.scores;
+
+    let mut recipes = Vec::<u8>::new();
+    recipes.extend([3, 7].iter());
+
+    let mut elves: [usize; 2] = [0, 1];
+    // Where the sequence might start, that hasn't been checked yet.
+    let mut next = 0;
+
+    while recipes.len() < MAX_RECIPES {
+        let new_recipe: u8 = elves.iter().map(|&elf_pos| recipes[elf_pos]).sum();
+        if new_recipe >= 10 {
+            recipes.push(new_recipe / 10);
+        }
+        recipes.push(new_recipe % 10);
+
+        for elf in elves.iter_mut() {
+            *elf = (*elf + (recipes[*elf] + 1) as usize) % recipes.len();
+        }
+
+        // End when the input sequence is generated in the list of recipes,
+        // checking every place it could start, in order - from the first
+        // recipe, as the sequence could be there already.
+        while next + score_seq.len() <= recipes.len() {
+            if recipes[next..next + score_seq.len()] == score_seq[..] {
+                return Ok(next)
+            }
+            next += 1;
+        }
+    }
+    Err(NoAnswer::new(format!("the scores don't come up in the first {} recipes", MAX_RECIPES)))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Scoreboard;
+    type Config = ();
+    type Answer1 = String;
+    type Answer2 = usize;
+    type Error = ParseError;
+
+    fn parse(puzzle: &str) -> Result<Scoreboard, ParseError> { parse(puzzle) }
+    fn part1(board: &Scoreboard, _: &()) -> Answer<String> { part1(board) }
+    fn part2(board: &Scoreboard, _: &()) -> Answer<usize> { part2(board) }
+}
+
+impl Generate for Day14 {
+    const SIZE: usize = 6;
+
+    // A number of `size` digits (up to 7, or part 1 would need billions of
+    // recipes), taken from somewhere in the first 100,000 recipe scores so
+    // that part 2 is sure to find it.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let digits = size.clamp(1, 7);
+        loop {
+            let at = rng.below(100_000);
+            let scores = part1(&Scoreboard{ recipes: at, scores: Vec::new() }).unwrap();
+            if !scores.starts_with('0') {
+                return format!("{}\n", &scores[..digits]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::generated!(Day14, 5);
+
+    #[test]
+    fn example_1() {
+        let tests = vec![
+            ("9",    "5158916779"),
+            ("5",    "0124515891"),
+            ("18",   "9251071085"),
+            ("2018", "5941429882"),
+        ];
+        for (test, expect) in tests {
+            assert_eq!(part1(&parse(test).unwrap()).as_deref(), Ok(expect));
+        }
+    }
+
+    #[test]
+    fn example_2() {
+        let tests = vec![
+            ("51589", 9),
+            ("01245", 5),
+            ("92510", 18),
+            ("59414", 2018),
+        ];
+        for (test, expect) in tests {
+            assert_eq!(part2(&parse(test).unwrap()), Ok(expect));
+        }
+    }
+
+    #[test]
+    fn bad_input() {
+        assert_eq!(parse("20-18").err(), Some(ParseError::BadDigit { pos: Pos::new(1, 3), found: '-' }));
+        assert_eq!(parse("99999999999999999999999").err().map(|e| e.pos()), Some(Pos::new(1, 1)));
+        assert_eq!(parse(" \n").err(), Some(ParseError::Empty));
+    }
+
+    #[test]
+    fn too_many() {
+        assert!(part1(&parse("18446744073709551615").unwrap()).is_err());
+        assert!(part1(&parse("49999991").unwrap()).is_err());
+    }
+
+    #[test]
+    fn against_reference() {
+        // Make plenty of recipes up front, then just look through them.
+        let mut board = vec![3u8, 7];
+        let (mut a, mut b) = (0, 1);
+        while board.len() < 200_000 {
+            let sum = board[a] + board[b];
+            board.extend(sum.to_string().bytes().map(|d| d - b'0'));
+            a = (a + 1 + board[a] as usize) % board.len();
+            b = (b + 1 + board[b] as usize) % board.len();
+        }
+
+        for size in 1..=5 {
+            for (seed, scoreboard) in aoc_common::generate::inputs::<Day14>(10, size) {
+                let after = board[scoreboard.recipes..scoreboard.recipes + 10].iter().map(|d| d.to_string()).collect::<String>();
+                assert_eq!(part1(&scoreboard), Ok(after), "seed {}, size {}", seed, size);
+
+                let found = board.windows(size).position(|w| w == scoreboard.scores.as_slice());
+                assert_eq!(part2(&scoreboard).ok(), found, "seed {}, size {}", seed, size);
+            }
+        }
+    }
+
+    #[test]
+    fn first_recipes() {
+        let find = |scores: &str| part2(&parse(scores).unwrap());
+        assert_eq!((find("3"), find("7"), find("37"), find("371")), (Ok(0), Ok(1), Ok(0), Ok(0)));
+    }
+}
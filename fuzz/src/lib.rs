@@ -0,0 +1,79 @@
+// What every day's fuzz target checks: that no input, however mangled, makes
+// the day panic, go on forever, or eat all the memory - whether it's turned
+// away by the parser (with a diagnostic that can be shown), or parsed and
+// solved, with the day's usual config.
+//
+//     cargo +nightly fuzz run day07 fuzz/corpus/day07 days/07/examples
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::Solution;
+use aoc_common::diagnostic;
+
+// Inputs much bigger than a real one are just slower to fuzz, not more
+// interesting - the biggest real input (day 5's) is about 50k.
+const MAX_INPUT: usize = 64 * 1024;
+// How long both parts get to answer. Real inputs take well under a second.
+const BUDGET: Duration = Duration::from_secs(10);
+// How much memory the heap can hold at once.
+const MAX_HEAP: usize = 1 << 30;
+
+// The system allocator, but refusing anything past `MAX_HEAP` - which aborts,
+// for the fuzzer to catch and keep the input that did it.
+struct Capped {
+    used: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for Capped {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if self.used.fetch_add(layout.size(), Ordering::Relaxed) + layout.size() > MAX_HEAP {
+            self.used.fetch_sub(layout.size(), Ordering::Relaxed);
+            return std::ptr::null_mut();
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.used.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Capped = Capped{ used: AtomicUsize::new(0) };
+
+// Parse and solve one input. Anything that isn't text never gets as far as
+// a day (the runner won't read it), so it's skipped.
+pub fn check<S: Solution>(data: &[u8]) where S::Input: Send {
+    let puzzle = match std::str::from_utf8(data) {
+        Ok(puzzle) if puzzle.len() <= MAX_INPUT => puzzle,
+        _ => return,
+    };
+    let input = match S::parse(puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            diagnostic::render(&e, puzzle, "fuzz");
+            return;
+        },
+    };
+
+    // Solve on another thread, so this one can keep time. A panic there
+    // aborts the whole process, the same as it would here.
+    let (done, finished) = mpsc::channel();
+    thread::spawn(move || {
+        let config = S::Config::default();
+        // No answer is fine (not every input has one), as long as the day
+        // says so rather than panicking.
+        let answers = (S::part1(&input, &config).map(|a| a.to_string()), S::part2(&input, &config).map(|a| a.to_string()));
+        let _ = done.send(answers);
+    });
+    match finished.recv_timeout(BUDGET) {
+        Ok(_) => {},
+        Err(RecvTimeoutError::Timeout) => panic!("day {:02} still going after {:?}", S::DAY, BUDGET),
+        Err(RecvTimeoutError::Disconnected) => panic!("day {:02} gave up without an answer", S::DAY),
+    }
+}